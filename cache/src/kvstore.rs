@@ -7,6 +7,7 @@ use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub type Result<T> = anyhow::Result<T>;
 
@@ -20,12 +21,12 @@ const COMPACTION_THRESHOLD: u64 = 1024 * 1024;
 /// A `BTreeMap` in memory stores the keys and the value locations for fast query.
 ///
 /// ```rust
-/// # use yakv::{KvStore, Result};
+/// # use leetup_cache::kvstore::{KvStore, Result};
 /// # fn try_main() -> Result<()> {
 /// use std::env::current_dir;
 /// let mut store = KvStore::open(current_dir()?)?;
 /// store.set("key".to_owned(), "value".to_owned())?;
-/// let val = store.get("key".to_owne())?;
+/// let val = store.get("key".to_owned())?;
 /// assert_eq!(val, Some("value".to_owned()));
 /// # Ok(())
 /// # }
@@ -56,6 +57,7 @@ impl KvStore {
         let ids = sorted_ids(&path)?;
         // println!("IDS: {:?}", ids);
         for &id in &ids {
+            restrict_permissions(&log_path(&path, id), 0o600)?;
             let mut reader = BufReaderWithPos::new(File::open(log_path(&path, id))?)?;
             stale_data += load_log(id, &mut reader, &mut index)?;
             readers.insert(id, reader);
@@ -76,16 +78,26 @@ impl KvStore {
 
     /// Sets the value of s string key to a string.
     pub fn set(&mut self, key: String, value: String) -> Result<()> {
-        let cmd = Command::set(key, value);
+        self.set_entry(key, value, None)
+    }
+
+    /// Sets the value of a string key to a string, expiring after `ttl`.
+    ///
+    /// Expired values are no longer returned by `get`, and dropped by `remove_expired`.
+    pub fn set_with_ttl(&mut self, key: String, value: String, ttl: Duration) -> Result<()> {
+        self.set_entry(key, value, Some(unix_timestamp() + ttl.as_secs()))
+    }
+
+    fn set_entry(&mut self, key: String, value: String, expires_at: Option<u64>) -> Result<()> {
+        let cmd = Command::set(key, value, expires_at);
         let pos = self.writer.pos;
         serde_json::to_writer(&mut self.writer, &cmd)?;
         self.writer.flush()?;
 
         if let Command::Set { key, .. } = cmd {
-            if let Some(old_cmd) = self.index.insert(
-                key,
-                CommandPos::from((self.current_id, pos..self.writer.pos)),
-            ) {
+            let mut cmd_pos = CommandPos::from((self.current_id, pos..self.writer.pos));
+            cmd_pos.expires_at = expires_at;
+            if let Some(old_cmd) = self.index.insert(key, cmd_pos) {
                 self.stale_data += old_cmd.len;
             }
         }
@@ -100,7 +112,18 @@ impl KvStore {
 
     /// Gets the string value for a given key.
     pub fn get(&mut self, key: String) -> Result<Option<String>> {
-        // println!("{:?}", self.index);
+        if self
+            .index
+            .get(&key)
+            .is_some_and(|cmd_pos| cmd_pos.is_expired(unix_timestamp()))
+        {
+            return Ok(None);
+        }
+        self.get_stale(key)
+    }
+
+    /// Gets the string value for a given key, even if it expired.
+    pub fn get_stale(&mut self, key: String) -> Result<Option<String>> {
         if let Some(cmd_pos) = self.index.get(&key) {
             let reader = self
                 .readers
@@ -120,7 +143,32 @@ impl KvStore {
 
     /// Check if key exists in the cache
     pub fn has_key(&self, key: String) -> bool {
-        self.index.contains_key(&key)
+        self.index
+            .get(&key)
+            .is_some_and(|cmd_pos| !cmd_pos.is_expired(unix_timestamp()))
+    }
+
+    /// Metadata of all entries, including the expired ones, ordered by key.
+    pub fn entries(&self) -> impl Iterator<Item = EntryInfo<'_>> {
+        self.index.iter().map(|(key, cmd_pos)| EntryInfo {
+            key,
+            size: cmd_pos.len,
+            expires_at: cmd_pos.expires_at,
+        })
+    }
+
+    /// Size in bytes of the log files.
+    pub fn log_size(&self) -> Result<u64> {
+        let mut size = 0;
+        for id in self.readers.keys() {
+            size += fs::metadata(log_path(&self.path, *id))?.len();
+        }
+        Ok(size)
+    }
+
+    /// Size in bytes of overwritten and removed values, reclaimed by compaction.
+    pub fn stale_size(&self) -> u64 {
+        self.stale_data
     }
 
     /// Removes the given key.
@@ -138,7 +186,29 @@ impl KvStore {
         }
     }
 
-    fn compact(&mut self) -> Result<()> {
+    /// Removes the expired keys, returns the number of removed keys.
+    ///
+    /// Expired values are otherwise kept, e.g. to be used offline,
+    /// until they are overwritten.
+    pub fn remove_expired(&mut self) -> Result<usize> {
+        let now = unix_timestamp();
+        let keys: Vec<String> = self
+            .index
+            .iter()
+            .filter(|(_, cmd_pos)| cmd_pos.is_expired(now))
+            .map(|(key, _)| key.to_owned())
+            .collect();
+        for key in &keys {
+            self.remove(key.to_owned())?;
+        }
+        Ok(keys.len())
+    }
+
+    /// Rewrites the key/value pairs to a new log file and scrubs the stale ones.
+    ///
+    /// Invoked once stale data reaches a threshold, or to make sure removed values,
+    /// e.g. credentials, are no longer on disk.
+    pub fn compact(&mut self) -> Result<()> {
         // increment id by 1
         // this will be used by compaction writer
         let compaction_id = self.current_id + 1;
@@ -155,7 +225,9 @@ impl KvStore {
 
             let mut cmd_reader = cmd_reader.take(cmd_pos.len);
             let len = io::copy(&mut cmd_reader, &mut compaction_writer)?;
+            let expires_at = cmd_pos.expires_at;
             *cmd_pos = CommandPos::from((compaction_id, new_pos..new_pos + len));
+            cmd_pos.expires_at = expires_at;
             new_pos += len;
         }
         compaction_writer.flush()?;
@@ -169,7 +241,7 @@ impl KvStore {
 
         for stale_id in stale_ids {
             self.readers.remove(&stale_id);
-            fs::remove_file(log_path(&self.path, stale_id))?;
+            scrub_file(&log_path(&self.path, stale_id))?;
         }
         self.stale_data = 0;
 
@@ -177,10 +249,43 @@ impl KvStore {
     }
 }
 
+// seconds elapsed since UNIX epoch
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 fn log_path<T: AsRef<Path>>(path: T, id: u64) -> PathBuf {
     path.as_ref().join(format!("{}.log", id))
 }
 
+// overwrite a file with zeros before removing it, so its records can not be
+// read back from the freed blocks
+fn scrub_file(path: &Path) -> Result<()> {
+    let len = fs::metadata(path)?.len();
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    io::copy(&mut io::repeat(0).take(len), &mut file)?;
+    file.sync_all()?;
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+// make log files only accessible by their owner, they may store credentials
+#[cfg(unix)]
+fn restrict_permissions(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
 fn create_log_file(
     id: u64,
     path: &Path,
@@ -188,6 +293,7 @@ fn create_log_file(
 ) -> Result<BufWriterWithPos<File>> {
     let path = log_path(&path, id);
     let writer = BufWriterWithPos::new(OpenOptions::new().create(true).append(true).open(&path)?)?;
+    restrict_permissions(&path, 0o600)?;
     readers.insert(id, BufReaderWithPos::new(File::open(&path)?)?);
     Ok(writer)
 }
@@ -205,8 +311,12 @@ fn load_log(
     while let Some(cmd) = stream.next() {
         let new_pos = stream.byte_offset() as u64;
         match cmd? {
-            Command::Set { key, .. } => {
-                if let Some(old_cmd) = index.insert(key, CommandPos::from((id, pos..new_pos))) {
+            Command::Set {
+                key, expires_at, ..
+            } => {
+                let mut cmd_pos = CommandPos::from((id, pos..new_pos));
+                cmd_pos.expires_at = expires_at;
+                if let Some(old_cmd) = index.insert(key, cmd_pos) {
                     stale_data += old_cmd.len;
                 }
             }
@@ -309,13 +419,25 @@ impl<T: Write + Seek> Seek for BufWriterWithPos<T> {
 /// Represent KV store commands
 #[derive(Serialize, Deserialize, Debug)]
 enum Command {
-    Set { key: String, value: String },
-    Remove { key: String },
+    Set {
+        key: String,
+        value: String,
+        /// UNIX timestamp after which the value expires, never if absent
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+    },
+    Remove {
+        key: String,
+    },
 }
 
 impl Command {
-    fn set(key: String, value: String) -> Self {
-        Command::Set { key, value }
+    fn set(key: String, value: String, expires_at: Option<u64>) -> Self {
+        Command::Set {
+            key,
+            value,
+            expires_at,
+        }
     }
 
     fn remove(key: String) -> Self {
@@ -323,14 +445,38 @@ impl Command {
     }
 }
 
+/// Metadata of an entry, listed by `KvStore::entries`
+#[derive(Debug)]
+pub struct EntryInfo<'a> {
+    pub key: &'a str,
+    /// Size in bytes of the entry in the log file
+    pub size: u64,
+    /// UNIX timestamp after which the value expires, never if `None`
+    pub expires_at: Option<u64>,
+}
+
+impl EntryInfo<'_> {
+    /// Check if the value expired at `now`
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
 /// Position for Command in log file
 ///
-/// Stores log file id, offset, and length
+/// Stores log file id, offset, length, and expiry of the value
 #[derive(Debug)]
 struct CommandPos {
     id: u64,
     pos: u64,
     len: u64,
+    expires_at: Option<u64>,
+}
+
+impl CommandPos {
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 impl From<(u64, Range<u64>)> for CommandPos {
@@ -339,6 +485,240 @@ impl From<(u64, Range<u64>)> for CommandPos {
             id,
             pos: range.start,
             len: range.end - range.start,
+            expires_at: None,
         }
     }
 }
+
+#[cfg(test)]
+fn read_logs(path: &Path) -> String {
+    sorted_ids(path)
+        .unwrap()
+        .into_iter()
+        .map(|id| String::from_utf8_lossy(&fs::read(log_path(path, id)).unwrap()).into_owned())
+        .collect()
+}
+
+#[test]
+fn test_compact_scrubs_stale_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = KvStore::open(dir.path()).unwrap();
+    store
+        .set("session".to_owned(), "old-session".to_owned())
+        .unwrap();
+    store
+        .set("session".to_owned(), "new-session".to_owned())
+        .unwrap();
+    store
+        .set("removed".to_owned(), "removed-session".to_owned())
+        .unwrap();
+    store.remove("removed".to_owned()).unwrap();
+    assert!(read_logs(dir.path()).contains("old-session"));
+    assert!(store.stale_size() > 0);
+
+    store.compact().unwrap();
+    let logs = read_logs(dir.path());
+    assert!(!logs.contains("old-session"));
+    assert!(!logs.contains("removed-session"));
+    assert!(logs.contains("new-session"));
+    assert_eq!(store.stale_size(), 0);
+    drop(store);
+
+    let mut store = KvStore::open(dir.path()).unwrap();
+    assert_eq!(
+        store.get("session".to_owned()).unwrap().as_deref(),
+        Some("new-session")
+    );
+    assert!(store.get("removed".to_owned()).unwrap().is_none());
+    assert_eq!(store.stale_size(), 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scrub_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("1.log");
+    fs::write(&path, "secret-session").unwrap();
+    // The removed file is still readable through an open handle
+    let mut file = File::open(&path).unwrap();
+
+    scrub_file(&path).unwrap();
+    assert!(!path.exists());
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).unwrap();
+    assert_eq!(content, vec![0; "secret-session".len()]);
+}
+
+#[test]
+fn test_ttl_expiry() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = KvStore::open(dir.path()).unwrap();
+    store
+        .set_with_ttl("expired".to_owned(), "old".to_owned(), Duration::ZERO)
+        .unwrap();
+    store
+        .set_with_ttl(
+            "fresh".to_owned(),
+            "new".to_owned(),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+    store.set("kept".to_owned(), "forever".to_owned()).unwrap();
+
+    assert!(store.get("expired".to_owned()).unwrap().is_none());
+    assert!(!store.has_key("expired".to_owned()));
+    assert_eq!(
+        store.get_stale("expired".to_owned()).unwrap().as_deref(),
+        Some("old")
+    );
+    assert_eq!(
+        store.get("fresh".to_owned()).unwrap().as_deref(),
+        Some("new")
+    );
+    assert_eq!(
+        store.get("kept".to_owned()).unwrap().as_deref(),
+        Some("forever")
+    );
+
+    let now = unix_timestamp();
+    let entries: Vec<_> = store.entries().collect();
+    assert_eq!(
+        entries.iter().map(|entry| entry.key).collect::<Vec<_>>(),
+        vec!["expired", "fresh", "kept"]
+    );
+    assert!(entries[0].is_expired(now));
+    assert!(!entries[1].is_expired(now));
+    assert!(entries[1].expires_at.unwrap() >= now + 3600);
+    assert!(entries[2].expires_at.is_none());
+
+    // Overwriting a value with a TTL clears it
+    store.set("fresh".to_owned(), "newer".to_owned()).unwrap();
+    assert!(store.stale_size() > 0);
+    assert!(store
+        .entries()
+        .all(|entry| entry.key != "fresh" || entry.expires_at.is_none()));
+}
+
+#[test]
+fn test_remove_expired() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = KvStore::open(dir.path()).unwrap();
+    store
+        .set_with_ttl("expired".to_owned(), "old".to_owned(), Duration::ZERO)
+        .unwrap();
+    store
+        .set_with_ttl(
+            "fresh".to_owned(),
+            "new".to_owned(),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+    assert_eq!(store.stale_size(), 0);
+
+    assert_eq!(store.remove_expired().unwrap(), 1);
+    assert!(store.get_stale("expired".to_owned()).unwrap().is_none());
+    assert!(store.stale_size() > 0);
+    assert_eq!(store.remove_expired().unwrap(), 0);
+    assert_eq!(store.entries().count(), 1);
+}
+
+#[test]
+fn test_reopen_with_ttl() {
+    let dir = tempfile::tempdir().unwrap();
+    // Logs written before TTLs were supported have no `expires_at`
+    fs::write(
+        log_path(dir.path(), 1),
+        r#"{"Set":{"key":"legacy","value":"old"}}"#,
+    )
+    .unwrap();
+
+    let mut store = KvStore::open(dir.path()).unwrap();
+    store
+        .set_with_ttl("expired".to_owned(), "old".to_owned(), Duration::ZERO)
+        .unwrap();
+    store
+        .set_with_ttl(
+            "fresh".to_owned(),
+            "new".to_owned(),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+    let expires_at: Vec<_> = store.entries().map(|entry| entry.expires_at).collect();
+    drop(store);
+
+    let mut store = KvStore::open(dir.path()).unwrap();
+    assert_eq!(
+        store
+            .entries()
+            .map(|entry| entry.expires_at)
+            .collect::<Vec<_>>(),
+        expires_at
+    );
+    assert_eq!(
+        store.get("legacy".to_owned()).unwrap().as_deref(),
+        Some("old")
+    );
+    assert!(store.get("expired".to_owned()).unwrap().is_none());
+    assert_eq!(
+        store.get_stale("expired".to_owned()).unwrap().as_deref(),
+        Some("old")
+    );
+    assert_eq!(
+        store.get("fresh".to_owned()).unwrap().as_deref(),
+        Some("new")
+    );
+}
+
+#[test]
+fn test_compact_keeps_ttl() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = KvStore::open(dir.path()).unwrap();
+    store
+        .set_with_ttl("expired".to_owned(), "old".to_owned(), Duration::ZERO)
+        .unwrap();
+    store
+        .set_with_ttl(
+            "fresh".to_owned(),
+            "stale".to_owned(),
+            Duration::from_secs(60),
+        )
+        .unwrap();
+    store
+        .set_with_ttl(
+            "fresh".to_owned(),
+            "new".to_owned(),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+    let expires_at: Vec<_> = store.entries().map(|entry| entry.expires_at).collect();
+
+    store.compact().unwrap();
+    assert_eq!(
+        store
+            .entries()
+            .map(|entry| entry.expires_at)
+            .collect::<Vec<_>>(),
+        expires_at
+    );
+    // Expired values are kept for offline use until removed
+    assert!(store.get("expired".to_owned()).unwrap().is_none());
+    assert_eq!(
+        store.get_stale("expired".to_owned()).unwrap().as_deref(),
+        Some("old")
+    );
+    drop(store);
+
+    let mut store = KvStore::open(dir.path()).unwrap();
+    assert_eq!(
+        store
+            .entries()
+            .map(|entry| entry.expires_at)
+            .collect::<Vec<_>>(),
+        expires_at
+    );
+    assert_eq!(
+        store.get("fresh".to_owned()).unwrap().as_deref(),
+        Some("new")
+    );
+    assert!(store.get("expired".to_owned()).unwrap().is_none());
+}
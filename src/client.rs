@@ -1,16 +1,43 @@
-use crate::{service::Session, Config, LeetUpError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{
+    expand_home,
+    service::{retry_after, Backoff, LazySession, Session},
+    Config, HttpConfig, LeetUpError, Result,
+};
 use anyhow::anyhow;
+use futures::stream::{self, Stream, StreamExt};
 use log::debug;
-use reqwest::{header, header::HeaderMap, header::HeaderValue, Client, Response};
+use rand::Rng;
+use reqwest::{
+    header, header::HeaderMap, header::HeaderValue, Certificate, Client, NoProxy, Proxy,
+    RequestBuilder, Response, StatusCode,
+};
+use url::Url;
+
+/// Upper bound of the delay between retries.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
 
+/// HTTP client shared by all requests of a run, reusing its connections.
 pub struct RemoteClient<'a> {
     config: &'a Config,
-    session: Option<&'a Session>,
+    session: &'a LazySession,
+    client: Client,
+    rate_limiter: RateLimiter,
 }
 
 impl<'a> RemoteClient<'_> {
-    pub fn new(config: &'a Config, session: Option<&'a Session>) -> RemoteClient<'a> {
-        RemoteClient { config, session }
+    pub fn new(config: &'a Config, session: &'a LazySession) -> Result<RemoteClient<'a>> {
+        Ok(RemoteClient {
+            config,
+            session,
+            client: build_client(&config.http)?,
+            rate_limiter: RateLimiter::new(config.http.rate_limit_interval()),
+        })
     }
 
     /// Make a GET request
@@ -21,8 +48,12 @@ impl<'a> RemoteClient<'_> {
         session: Option<&Session>,
     ) -> Result<Response> {
         let headers = self.headers_with_session(headers_opt, session);
-        let client = Client::builder().default_headers(headers).build()?;
-        client.get(url).send().await.map_err(LeetUpError::Reqwest)
+        let res = self
+            .send(url, self.client.get(url).headers(headers), true)
+            .await?;
+        check_session(res.status(), session)?;
+
+        Ok(res)
     }
 
     /// Make a POST request
@@ -35,19 +66,38 @@ impl<'a> RemoteClient<'_> {
     where
         F: FnOnce() -> Option<HeaderMap>,
     {
-        let headers = self.headers_with_session(with_headers(), self.session);
+        self.post_with_session(url, body, with_headers, self.session.get()?)
+            .await
+    }
+
+    /// Make a POST request using the given `session` instead of the cached one
+    pub async fn post_with_session<T: serde::Serialize + ?Sized, F>(
+        &self,
+        url: &str,
+        body: &T,
+        with_headers: F,
+        session: Option<&Session>,
+    ) -> Result<serde_json::value::Value>
+    where
+        F: FnOnce() -> Option<HeaderMap>,
+    {
+        let headers = self.headers_with_session(with_headers(), session);
         debug!("Headers: {:#?}", headers);
-        let client = Client::builder().default_headers(headers).build()?;
 
-        let client = client
+        let request = self
+            .client
             .post(url)
+            .headers(headers)
             .header(
                 header::ORIGIN,
                 HeaderValue::from_str(&self.config.urls.base).unwrap(),
             )
             .json(body);
 
-        let res = client.send().await?;
+        // GraphQL requests are queries only, unlike e.g. submissions
+        let idempotent = url == self.config.urls.graphql;
+        let res = self.send(url, request, idempotent).await?;
+        check_session(res.status(), session)?;
 
         if res.status() == 200 {
             res.json::<serde_json::value::Value>()
@@ -58,6 +108,59 @@ impl<'a> RemoteClient<'_> {
         }
     }
 
+    /// Run `request` on each of `items` with at most `limit` requests in flight,
+    /// `http.max_concurrency` by default, yielding the results as they complete.
+    pub fn execute_all<'c, T, F, Fut>(
+        &'c self,
+        items: Vec<T>,
+        limit: Option<usize>,
+        request: F,
+    ) -> impl Stream<Item = Fut::Output> + 'c
+    where
+        T: 'c,
+        F: FnMut(T) -> Fut + 'c,
+        Fut: Future + 'c,
+    {
+        let limit = limit.unwrap_or(self.config.http.max_concurrency).max(1);
+        stream::iter(items).map(request).buffer_unordered(limit)
+    }
+
+    /// Send `request` to `url` once the rate limit of its host allows it.
+    ///
+    /// `idempotent` requests are retried with jittered exponential backoff
+    /// on connection errors and transient 5xx responses.
+    async fn send(&self, url: &str, request: RequestBuilder, idempotent: bool) -> Result<Response> {
+        if self.config.offline {
+            return Err(LeetUpError::Offline(url.to_owned()));
+        }
+        let http = &self.config.http;
+        let retries = if idempotent { http.retries } else { 0 };
+        let mut backoff = Backoff::new(http.retry_delay(), MAX_RETRY_DELAY);
+
+        for attempt in 0.. {
+            let retry = match request.try_clone() {
+                Some(retry) if attempt < retries => retry,
+                _ => break,
+            };
+            self.rate_limiter.wait(url).await;
+            let delay = match retry.send().await {
+                Ok(res) if is_transient(res.status()) => retry_after(&res)
+                    .map(|delay| delay.min(MAX_RETRY_DELAY))
+                    .unwrap_or_else(|| jitter(backoff.next().unwrap_or_default())),
+                Err(e) if e.is_connect() || e.is_timeout() || e.is_request() => {
+                    debug!("Request to {} failed: {}", url, e);
+                    jitter(backoff.next().unwrap_or_default())
+                }
+                res => return Ok(res?),
+            };
+            debug!("Retrying {} in {:?}, attempt {}", url, delay, attempt + 1);
+            tokio::time::sleep(delay).await;
+        }
+
+        self.rate_limiter.wait(url).await;
+        Ok(request.send().await?)
+    }
+
     fn headers_with_session(
         &self,
         headers_opt: Option<HeaderMap>,
@@ -78,3 +181,151 @@ impl<'a> RemoteClient<'_> {
         headers
     }
 }
+
+/// Build the client shared by all requests from the `http` settings.
+fn build_client(http: &HttpConfig) -> Result<Client> {
+    let mut builder = Client::builder().connect_timeout(http.connect_timeout());
+    if let Some(timeout) = http.timeout() {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(ref proxy) = http.proxy {
+        let proxy = Proxy::all(proxy)
+            .map_err(|e| LeetUpError::Any(anyhow!("Invalid proxy {}: {}", proxy, e)))?;
+        builder = builder.proxy(proxy.no_proxy(NoProxy::from_env()));
+    }
+    if let Some(ref path) = http.ca_certificate {
+        let path = expand_home(path);
+        let pem = fs::read(&path).map_err(|e| {
+            LeetUpError::Any(anyhow!(
+                "Unable to read CA certificate {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let certificates = Certificate::from_pem_bundle(&pem).unwrap_or_default();
+        if certificates.is_empty() {
+            return Err(LeetUpError::Any(anyhow!(
+                "No PEM certificate found in {}",
+                path.display()
+            )));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Whether a response with `status` may succeed if the request is sent again.
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Random delay between half of `delay` and `delay`, so that retries are spread out.
+fn jitter(delay: Duration) -> Duration {
+    delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+/// Spaces out requests to the same host by at least `interval`.
+struct RateLimiter {
+    interval: Option<Duration>,
+    /// Instant from which the next request to a host may be sent.
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    fn new(interval: Option<Duration>) -> Self {
+        RateLimiter {
+            interval,
+            next: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for the next slot of the host of `url`.
+    async fn wait(&self, url: &str) {
+        let host = Url::parse(url)
+            .map(|url| url.origin().ascii_serialization())
+            .unwrap_or_default();
+        let slot = self.reserve(host, Instant::now());
+        tokio::time::sleep_until(slot.into()).await;
+    }
+
+    /// Reserve the earliest slot of `host` at or after `now`.
+    fn reserve(&self, host: String, now: Instant) -> Instant {
+        let interval = match self.interval {
+            Some(interval) => interval,
+            None => return now,
+        };
+        let mut next = self.next.lock().unwrap();
+        let slot = next.get(&host).map_or(now, |&next| next.max(now));
+        next.insert(host, slot + interval);
+
+        slot
+    }
+}
+
+/// Fail with a hint to login again if the judge rejected the `session`.
+fn check_session(status: StatusCode, session: Option<&Session>) -> Result<()> {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN if session.is_some() => {
+            Err(LeetUpError::SessionExpired(status))
+        }
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(LeetUpError::LoginRequired(status)),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_check_session() {
+    let session = Session::new("session-id".into(), "csrf-token".into());
+    assert!(check_session(StatusCode::OK, Some(&session)).is_ok());
+    assert!(matches!(
+        check_session(StatusCode::FORBIDDEN, Some(&session)),
+        Err(LeetUpError::SessionExpired(StatusCode::FORBIDDEN))
+    ));
+    assert!(matches!(
+        check_session(StatusCode::UNAUTHORIZED, None),
+        Err(LeetUpError::LoginRequired(StatusCode::UNAUTHORIZED))
+    ));
+}
+
+#[test]
+fn test_rate_limiter() {
+    let now = Instant::now();
+    let interval = Duration::from_millis(100);
+    let limiter = RateLimiter::new(Some(interval));
+    let host = || "https://leetcode.com".to_string();
+    assert_eq!(now, limiter.reserve(host(), now));
+    assert_eq!(now + interval, limiter.reserve(host(), now));
+    assert_eq!(now + interval * 2, limiter.reserve(host(), now));
+    // Hosts are limited independently
+    assert_eq!(now, limiter.reserve("https://leetcode.cn".into(), now));
+    // Slots are not saved up while idle
+    let later = now + interval * 10;
+    assert_eq!(later, limiter.reserve(host(), later));
+
+    let unlimited = RateLimiter::new(None);
+    assert_eq!(now, unlimited.reserve(host(), now));
+    assert_eq!(now, unlimited.reserve(host(), now));
+}
+
+#[test]
+fn test_retry_policy() {
+    assert!(is_transient(StatusCode::SERVICE_UNAVAILABLE));
+    assert!(is_transient(StatusCode::BAD_GATEWAY));
+    assert!(!is_transient(StatusCode::NOT_FOUND));
+    assert!(!is_transient(StatusCode::TOO_MANY_REQUESTS));
+
+    let delay = Duration::from_millis(400);
+    for _ in 0..100 {
+        let jittered = jitter(delay);
+        assert!(jittered >= delay / 2 && jittered <= delay);
+    }
+}
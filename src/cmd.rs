@@ -1,14 +1,15 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use leetup_cache::kvstore::KvStore;
 use log::debug;
-use spinners::{Spinner, Spinners};
 use structopt::StructOpt;
 
-use crate::service::{CacheKey, Session};
+use crate::printer::OutputFormat;
+use crate::service::{CacheKey, LazySession};
+use crate::style::{self, ColorChoice};
 use crate::{
     service::{leetcode::Leetcode, Lang, ServiceProvider},
-    Config, Result,
+    Config, Result, Site,
 };
 
 #[derive(Debug, StructOpt)]
@@ -41,12 +42,21 @@ pub struct User {
     /// Logout user
     #[structopt(short, long)]
     pub logout: Option<Option<String>>,
+
+    /// Login using cookies exported from a browser, either a cookies.txt file
+    /// or a copy of Firefox's cookies.sqlite
+    #[structopt(long, parse(from_os_str))]
+    pub import_cookies: Option<PathBuf>,
+
+    /// Show the logged in user, premium status and session expiry
+    #[structopt(long)]
+    pub status: bool,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct Pick {
-    /// Show/Pick a problem using ID.
-    pub id: Option<usize>,
+    /// Show/Pick a problem using ID, title slug or title.
+    pub id: Option<String>,
 
     /// Generate code if true.
     #[structopt(short)]
@@ -59,6 +69,51 @@ pub struct Pick {
     /// Language used to generate problem's source.
     #[structopt(short, long)]
     pub lang: Option<Lang>,
+
+    /// Pick a random unsolved problem.
+    #[structopt(short, long)]
+    pub random: bool,
+
+    /// Query random problem by conditions, same as `list -q`.
+    #[structopt(short, long)]
+    pub query: Option<String>,
+
+    /// Pick random problem with given tag.
+    #[structopt(short, long)]
+    pub tag: Option<String>,
+
+    /// Skip random problems picked within given number of days.
+    #[structopt(long, default_value = "30")]
+    pub days: u64,
+
+    /// Download the problem again instead of using the cached one.
+    #[structopt(long)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Daily {
+    /// Generate code if true.
+    #[structopt(short)]
+    pub generate: bool,
+
+    /// Language used to generate problem's source.
+    #[structopt(short, long)]
+    pub lang: Option<Lang>,
+
+    /// Download the problem again instead of using the cached one.
+    #[structopt(long)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Show {
+    /// Show a problem using ID, title slug or title.
+    pub id: String,
+
+    /// Download the problem again instead of using the cached one.
+    #[structopt(long)]
+    pub refresh: bool,
 }
 
 #[derive(Debug, StructOpt)]
@@ -67,6 +122,20 @@ pub struct Submit {
     pub filename: String,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct Submissions {
+    /// Code filename, problem ID, title slug or title.
+    pub problem: String,
+
+    /// Download a submission by ID, or the last accepted one, into a source file.
+    #[structopt(short, long)]
+    pub download: Option<Option<u64>>,
+
+    /// Number of recent submissions to fetch.
+    #[structopt(short = "n", long, default_value = "20")]
+    pub limit: usize,
+}
+
 #[derive(Debug, StructOpt)]
 pub struct Test {
     /// Code filename.
@@ -77,6 +146,27 @@ pub struct Test {
     pub test_data: Option<Option<String>>,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct Run {
+    /// Code filename.
+    pub filename: String,
+
+    /// Custom test cases, defaults to the sample test cases of the problem.
+    #[structopt(short)]
+    pub test_data: Option<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Sync {
+    /// Number of problems downloaded concurrently, defaults to `http.max_concurrency` in config.
+    #[structopt(short, long)]
+    pub jobs: Option<usize>,
+
+    /// Download all problems again instead of skipping the cached ones.
+    #[structopt(long)]
+    pub refresh: bool,
+}
+
 #[derive(Debug, StructOpt)]
 pub enum Command {
     /// List questions
@@ -91,6 +181,14 @@ pub enum Command {
     #[structopt(name = "pick")]
     Pick(Pick),
 
+    /// Show a problem
+    #[structopt(name = "show")]
+    Show(Show),
+
+    /// Show/Pick the daily challenge
+    #[structopt(name = "daily")]
+    Daily(Daily),
+
     /// Submit a problem
     #[structopt(name = "submit")]
     Submit(Submit),
@@ -98,6 +196,41 @@ pub enum Command {
     /// Test a problem
     #[structopt(name = "test")]
     Test(Test),
+
+    /// Run a problem locally against sample test cases
+    #[structopt(name = "run")]
+    Run(Run),
+
+    /// List and download submissions of a problem
+    #[structopt(name = "submissions")]
+    Submissions(Submissions),
+
+    /// Manage the cache of problems
+    #[structopt(name = "cache")]
+    Cache(Cache),
+
+    /// Download all free problems to use them offline
+    #[structopt(name = "sync")]
+    Sync(Sync),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Cache {
+    /// Show cached entries and the size of the cache
+    #[structopt(name = "stats")]
+    Stats,
+
+    /// Fetch the list of problems again
+    #[structopt(name = "refresh")]
+    Refresh,
+
+    /// Remove an entry, e.g. `problems`, or all entries except the session
+    #[structopt(name = "clear")]
+    Clear { key: Option<String> },
+
+    /// Drop stale and expired entries from the cache files
+    #[structopt(name = "compact")]
+    Compact,
 }
 
 /// -q to query by conditions.
@@ -144,6 +277,7 @@ impl From<char> for Query {
 }
 
 impl Query {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(q: &str) -> Vec<Query> {
         q.chars().map(Query::from).collect()
     }
@@ -176,6 +310,7 @@ impl From<char> for OrderBy {
 }
 
 impl OrderBy {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(order: &str) -> Vec<OrderBy> {
         order.chars().map(OrderBy::from).collect()
     }
@@ -186,6 +321,26 @@ impl OrderBy {
 pub struct LeetUpArgs {
     #[structopt(subcommand)]
     pub command: Command,
+
+    /// Site to use: leetcode.com or leetcode.cn, overrides `site` in config.
+    #[structopt(long, global = true)]
+    pub site: Option<Site>,
+
+    /// Named profile with its own session, cache and default `lang`.
+    #[structopt(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Work solely from the cache, see `leetup sync`.
+    #[structopt(long, global = true)]
+    pub offline: bool,
+
+    /// Output format of results: text, plain (no colors) or json.
+    #[structopt(long, global = true)]
+    pub output: Option<OutputFormat>,
+
+    /// When to use colors and unicode icons: auto, always or never.
+    #[structopt(long, global = true, default_value = "auto")]
+    pub color: ColorChoice,
 }
 
 pub async fn process() -> Result<()> {
@@ -193,18 +348,40 @@ pub async fn process() -> Result<()> {
     debug!("Options: {:#?}", opt);
 
     let config_dir = create_config_directory()?;
-    let mut cache = KvStore::open(&config_dir)?;
-    let session = get_session(&mut cache)?;
-    let config = get_config(config_dir);
-    debug!("Session: {:#?}", session);
+    let mut config = get_config(config_dir.clone())?;
+    if let Some(site) = opt.site {
+        config.set_site(site);
+    }
+    if let Some(ref profile) = opt.profile {
+        config.set_profile(profile)?;
+    }
+    config.offline = opt.offline;
+    config.output = opt.output.unwrap_or_default();
+    style::init(if config.output == OutputFormat::Text {
+        opt.color
+    } else {
+        ColorChoice::Never
+    });
+    let mut cache = KvStore::open(config.site.cache_dir(&config_dir))?;
+    // Decrypted when first needed, logout or clearing the cache never need it
+    let session = LazySession::new(
+        cache.get(CacheKey::Session.scoped(config.profile.as_deref()))?,
+        config.session_encryption.clone(),
+    );
     debug!("Config: {:#?}", config);
 
-    let mut provider = Leetcode::new(session.as_ref(), &config, cache)?;
+    let mut provider = Leetcode::new(&session, &config, cache)?;
 
     match opt.command {
         Command::Pick(pick) => {
             provider.pick_problem(pick).await?;
         }
+        Command::Show(show) => {
+            provider.show_problem(show).await?;
+        }
+        Command::Daily(daily) => {
+            provider.daily_problem(daily).await?;
+        }
         Command::List(list) => {
             provider.list_problems(list).await?;
         }
@@ -212,44 +389,65 @@ pub async fn process() -> Result<()> {
             provider.process_auth(user).await?;
         }
         Command::Submit(submit) => {
-            let sp = Spinner::new(Spinners::Dots9, "Waiting for judge result!".into());
             provider.problem_submit(submit).await?;
-            sp.stop();
         }
         Command::Test(test) => {
-            let sp = Spinner::new(Spinners::Dots9, "Waiting for judge result!".into());
             provider.problem_test(test).await?;
-            sp.stop();
+        }
+        Command::Run(run) => {
+            provider.problem_run(run).await?;
+        }
+        Command::Submissions(submissions) => {
+            provider.problem_submissions(submissions).await?;
+        }
+        Command::Cache(cache) => {
+            provider.process_cache(cache).await?;
+        }
+        Command::Sync(sync) => {
+            provider.sync_problems(sync).await?;
         }
     }
     Ok(())
 }
 
-fn get_config(mut config_dir: PathBuf) -> Config {
+fn get_config(mut config_dir: PathBuf) -> Result<Config> {
     config_dir.push("config.json");
     Config::get(config_dir)
 }
 
-fn get_session(cache: &mut KvStore) -> Result<Option<Session>> {
-    let mut session: Option<Session> = None;
-    let session_val = cache.get(CacheKey::Session.into())?;
+fn create_config_directory() -> Result<PathBuf> {
+    // LEETUP_HOME overrides ~/.leetup, useful to isolate tests
+    let data_dir = match std::env::var_os("LEETUP_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            // create .leetup directory: ~/.leetup/*.log
+            let mut data_dir = PathBuf::new();
+            data_dir.push(
+                dirs::home_dir()
+                    .ok_or("Home directory not available!")
+                    .map_err(anyhow::Error::msg)?,
+            );
+            data_dir.push(".leetup");
+            data_dir
+        }
+    };
+    std::fs::create_dir_all(&data_dir)?;
+    restrict_permissions(&data_dir)?;
 
-    // Set session if the user is logged in
-    if let Some(ref val) = session_val {
-        session = Some(serde_json::from_str::<Session>(val)?);
-    }
-    Ok(session)
+    Ok(data_dir)
 }
 
-fn create_config_directory() -> Result<PathBuf> {
-    // create .leetup directory: ~/.leetup/*.log
-    let mut data_dir = PathBuf::new();
-    data_dir.push(
-        dirs::home_dir()
-            .ok_or("Home directory not available!")
-            .map_err(anyhow::Error::msg)?,
-    );
-    data_dir.push(".leetup");
+/// Make the config directory only accessible by its owner, the cache stores
+/// session credentials.
+#[cfg(unix)]
+fn restrict_permissions(dir: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
 
-    Ok(data_dir)
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_dir: &Path) -> Result<()> {
+    Ok(())
 }
@@ -1,12 +1,15 @@
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
-use std::{collections::HashMap, str::FromStr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{collections::HashMap, fmt, str::FromStr};
 
+use anyhow::anyhow;
 use log::warn;
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de, de::DeserializeOwned, Deserialize, Serialize};
+use url::Url;
 
-use crate::{service::Lang, LeetUpError, Result};
+use crate::{printer::OutputFormat, service::Lang, LeetUpError, Result};
 
 type LangInjectCode = HashMap<String, InjectCode>;
 type PickHookConfig = HashMap<String, PickHook>;
@@ -18,44 +21,123 @@ pub struct Config {
     pub inject_code: Option<LangInjectCode>,
     pub pick_hook: Option<PickHookConfig>,
     pub lang: Lang,
+    #[serde(default)]
+    pub site: Site,
+    #[serde(default, deserialize_with = "deserialize_judge")]
+    pub judge: JudgeConfig,
+    #[serde(default, deserialize_with = "deserialize_run")]
+    pub run: RunConfig,
+    #[serde(default, deserialize_with = "deserialize_cache")]
+    pub cache: CacheConfig,
+    #[serde(default, deserialize_with = "deserialize_http")]
+    pub http: HttpConfig,
+    /// Settings of named profiles, e.g. `{"work": {"lang": "java"}}`.
+    #[serde(default, deserialize_with = "deserialize_profiles")]
+    pub profiles: HashMap<String, Profile>,
+    /// Encryption at rest of the cached session, stored as plain JSON if `None`.
+    #[serde(default)]
+    pub session_encryption: Option<SessionEncryption>,
+    /// Profile in use, from `--profile`. The default profile if `None`.
+    #[serde(skip)]
+    pub profile: Option<String>,
+    /// Work solely from the cache, from `--offline`.
+    #[serde(skip)]
+    pub offline: bool,
+    /// Format of command results, from `--output`.
+    #[serde(skip)]
+    pub output: OutputFormat,
+    #[serde(
+        default,
+        rename = "urls",
+        deserialize_with = "deserialize_url_overrides"
+    )]
+    url_overrides: Option<UrlOverrides>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let site = Site::default();
+        Config {
+            urls: Urls::new(site.base()),
+            inject_code: None,
+            pick_hook: None,
+            lang: Lang::from_str("rust").unwrap(),
+            site,
+            judge: JudgeConfig::default(),
+            run: RunConfig::default(),
+            cache: CacheConfig::default(),
+            http: HttpConfig::default(),
+            profiles: HashMap::new(),
+            session_encryption: None,
+            profile: None,
+            offline: false,
+            output: OutputFormat::default(),
+            url_overrides: None,
+        }
+    }
 }
 
 impl Config {
-    pub fn get<P: AsRef<Path>>(path: P) -> Self {
-        let base = "https://leetcode.com";
-        let urls = Urls {
-            base: base.to_owned(),
-            api: format!("{}/api", base),
-            graphql: format!("{}/graphql", base),
-            problems: format!("{}/problems/", base),
-            problems_all: format!("{}/api/problems/all", base),
-            github_login: format!("{}/accounts/github/login/?next=%2F", base),
-            github_login_request: "https://github.com/login".to_string(),
-            github_session_request: "https://github.com/session".to_string(),
-            test: format!("{}/problems/$slug/interpret_solution/", base),
-            submit: format!("{}/problems/$slug/submit/", base),
-            submissions: format!("{}/api/submissions/$slug", base),
-            submission: format!("{}/submissions/detail/$id", base),
-            verify: format!("{}/submissions/detail/$id/check/", base),
-        };
+    /// Read config from `path`, defaults are used if it does not exist.
+    ///
+    /// Fail if it exists but can not be parsed, rather than silently dropping
+    /// settings like `session_encryption`.
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            warn!("{} not found, using defaults", path.display());
+            return Ok(Config::default());
+        }
 
-        let config: Result<Config> = Config::get_config(path);
+        let mut config: Config = Config::get_config(path)
+            .map_err(|e| LeetUpError::Any(anyhow!("Invalid config {}: {}", path.display(), e)))?;
+        config.build_urls();
+        Ok(config)
+    }
 
-        match config {
-            Ok(mut c) => {
-                c.urls = urls.clone();
-                c
-            }
+    /// Switch to another site, e.g. from `--site` flag.
+    ///
+    /// Endpoint overrides still apply, but not the overridden `base`.
+    pub fn set_site(&mut self, site: Site) {
+        self.site = site;
+        if let Some(ref mut overrides) = self.url_overrides {
+            overrides.base = None;
+        }
+        self.build_urls();
+    }
+
+    /// Switch to the named profile, e.g. from `--profile` flag.
+    ///
+    /// Profiles have their own session and cache, and may override `lang`.
+    pub fn set_profile(&mut self, name: &str) -> Result<()> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(LeetUpError::Any(anyhow!(
+                "Invalid profile `{}`: expected letters, digits, `-` or `_`",
+                name
+            )));
+        }
+        if let Some(lang) = self.profiles.get(name).and_then(|p| p.lang.clone()) {
+            self.lang = lang;
+        }
+        self.profile = Some(name.to_owned());
+
+        Ok(())
+    }
+
+    fn build_urls(&mut self) {
+        let overrides = self.url_overrides.take().unwrap_or_default();
+        self.urls = match Urls::with_overrides(self.site.base(), &overrides) {
+            Ok(urls) => urls,
             Err(e) => {
-                warn!("{:#?}", e);
-                Config {
-                    urls,
-                    inject_code: None,
-                    pick_hook: None,
-                    lang: Lang::from_str("rust").unwrap(),
-                }
+                warn!("Ignoring `urls` in config: {}", e);
+                Urls::new(self.site.base())
             }
-        }
+        };
+        self.url_overrides = Some(overrides);
     }
 
     fn get_config<P: AsRef<Path>, T: DeserializeOwned>(path: P) -> Result<T> {
@@ -67,7 +149,177 @@ impl Config {
     }
 }
 
-#[derive(Deserialize, Debug)]
+/// Settings of a named profile in config.json.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Default language of the profile, overrides `lang`.
+    pub lang: Option<Lang>,
+}
+
+/// Expiry of cached responses.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Refresh the list of problems after this many seconds, never if 0.
+    pub problems_ttl: u64,
+
+    /// Refresh the detail of a problem after this many seconds, never if 0.
+    pub problem_ttl: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            problems_ttl: 24 * 60 * 60,
+            problem_ttl: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn problems_ttl(&self) -> Option<Duration> {
+        (self.problems_ttl > 0).then(|| Duration::from_secs(self.problems_ttl))
+    }
+
+    /// Whether the detail of a problem fetched at `fetched_at` is stale at `now`.
+    pub fn is_problem_stale(&self, fetched_at: u64, now: u64) -> bool {
+        self.problem_ttl > 0 && fetched_at.saturating_add(self.problem_ttl) <= now
+    }
+}
+
+/// Requests to the judge.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    /// Maximum number of requests in flight during bulk operations, e.g. `sync`.
+    pub max_concurrency: usize,
+
+    /// Maximum number of requests per second to the same host, unlimited if 0.
+    pub rate_limit: u32,
+
+    /// Give up connecting to the judge after this many seconds.
+    pub connect_timeout: u64,
+
+    /// Give up waiting for a complete response after this many seconds, never if 0.
+    pub timeout: u64,
+
+    /// Number of retries of idempotent requests failing with a connection error or 5xx.
+    pub retries: u32,
+
+    /// Delay before the first retry in milliseconds, doubled after each retry.
+    pub retry_delay: u64,
+
+    /// Proxy of all requests, e.g. `http://proxy:3128`. `HTTPS_PROXY` and `HTTP_PROXY` apply if `None`.
+    pub proxy: Option<String>,
+
+    /// PEM file of CA certificates trusted in addition to the system ones.
+    pub ca_certificate: Option<PathBuf>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            max_concurrency: 4,
+            rate_limit: 10,
+            connect_timeout: 10,
+            timeout: 30,
+            retries: 2,
+            retry_delay: 500,
+            proxy: None,
+            ca_certificate: None,
+        }
+    }
+}
+
+impl HttpConfig {
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout)
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.timeout > 0).then(|| Duration::from_secs(self.timeout))
+    }
+
+    pub fn retry_delay(&self) -> Duration {
+        Duration::from_millis(self.retry_delay)
+    }
+
+    /// Minimum delay between two requests to the same host.
+    pub fn rate_limit_interval(&self) -> Option<Duration> {
+        (self.rate_limit > 0).then(|| Duration::from_secs(1) / self.rate_limit)
+    }
+}
+
+/// Source of the key encrypting the cached session.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEncryption {
+    /// Key derived from a passphrase, read from `LEETUP_PASSPHRASE` or prompted.
+    Passphrase,
+    /// Key derived from the content of a file, e.g. `{"key_file": "~/.leetup.key"}`.
+    KeyFile(PathBuf),
+}
+
+/// Polling of the judge result after `test` and `submit`.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JudgeConfig {
+    /// Stop waiting for the result after this many seconds.
+    pub timeout: u64,
+
+    /// Delay before the first poll in milliseconds, doubled after each poll.
+    pub poll_interval: u64,
+
+    /// Upper bound of the delay between polls in milliseconds.
+    pub max_poll_interval: u64,
+}
+
+impl Default for JudgeConfig {
+    fn default() -> Self {
+        JudgeConfig {
+            timeout: 120,
+            poll_interval: 200,
+            max_poll_interval: 3200,
+        }
+    }
+}
+
+impl JudgeConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout)
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+
+    pub fn max_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.max_poll_interval.max(self.poll_interval))
+    }
+}
+
+/// Local runs of solutions with `run`.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    /// Kill the solution after this many seconds, never if 0.
+    pub timeout: u64,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        RunConfig { timeout: 10 }
+    }
+}
+
+impl RunConfig {
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.timeout > 0).then(|| Duration::from_secs(self.timeout))
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(untagged)]
 pub enum Either {
     Sequence(Vec<String>),
@@ -85,13 +337,65 @@ impl From<String> for Either {
     }
 }
 
-impl ToString for Either {
-    fn to_string(&self) -> String {
+impl fmt::Display for Either {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Either::String(s) => write!(f, "{}", s),
+            Either::Sequence(v) => write!(f, "{}", v.join("\n")),
+        }
+    }
+}
+
+/// Online Judge sites supported by leetup.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Site {
+    #[default]
+    LeetcodeCom,
+    LeetcodeCn,
+}
+
+impl Site {
+    pub fn base(&self) -> &'static str {
+        match self {
+            Site::LeetcodeCom => "https://leetcode.com",
+            Site::LeetcodeCn => "https://leetcode.cn",
+        }
+    }
+
+    /// Directory of the cache, sessions and problems are not shared between sites.
+    pub fn cache_dir(&self, config_dir: &Path) -> PathBuf {
         match self {
-            Either::String(s) => s.to_owned(),
-            Either::Sequence(v) => v.join("\n"),
+            Site::LeetcodeCom => config_dir.to_path_buf(),
+            Site::LeetcodeCn => config_dir.join("leetcode.cn"),
         }
     }
+
+    /// Whether the site serves translated content of problems.
+    pub fn is_translated(&self) -> bool {
+        matches!(self, Site::LeetcodeCn)
+    }
+}
+
+impl FromStr for Site {
+    type Err = LeetUpError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "leetcode.com" | "com" => Ok(Site::LeetcodeCom),
+            "leetcode.cn" | "cn" => Ok(Site::LeetcodeCn),
+            _ => Err(LeetUpError::Any(anyhow!("Site not supported!"))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Site {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Site::from_str(&s).map_err(de::Error::custom)
+    }
 }
 
 #[derive(Debug, Default, Deserialize, Clone)]
@@ -111,6 +415,209 @@ pub struct Urls {
     pub verify: String,
 }
 
+/// Overrides of `Urls` from config.json.
+///
+/// Endpoints are either absolute URLs or paths relative to `base`,
+/// keeping placeholders like `$slug` and `$id`.
+#[derive(Debug, Default, Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrlOverrides {
+    pub base: Option<String>,
+    pub api: Option<String>,
+    pub graphql: Option<String>,
+    pub problems: Option<String>,
+    pub problems_all: Option<String>,
+    pub test: Option<String>,
+    pub submit: Option<String>,
+    pub submissions: Option<String>,
+    pub submission: Option<String>,
+    pub verify: Option<String>,
+}
+
+/// Expand a leading `~` of `path` to the home directory.
+pub(crate) fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Ignore invalid `urls` in config instead of failing the whole config.
+fn deserialize_url_overrides<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<UrlOverrides>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    match serde_json::from_value(value) {
+        Ok(overrides) => Ok(Some(overrides)),
+        Err(e) => {
+            warn!("Ignoring `urls` in config: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+/// Deserialize a section of config, or use its defaults if it is invalid
+/// so that the rest of config still applies.
+fn section_or_default<T>(name: &str, value: serde_json::Value) -> T
+where
+    T: DeserializeOwned + Default,
+{
+    serde_json::from_value(value).unwrap_or_else(|e| {
+        warn!("Ignoring `{}` in config: {}", name, e);
+        T::default()
+    })
+}
+
+fn deserialize_judge<'de, D>(deserializer: D) -> std::result::Result<JudgeConfig, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(section_or_default("judge", value))
+}
+
+fn deserialize_run<'de, D>(deserializer: D) -> std::result::Result<RunConfig, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(section_or_default("run", value))
+}
+
+fn deserialize_cache<'de, D>(deserializer: D) -> std::result::Result<CacheConfig, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(section_or_default("cache", value))
+}
+
+fn deserialize_http<'de, D>(deserializer: D) -> std::result::Result<HttpConfig, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(section_or_default("http", value))
+}
+
+/// Ignore invalid profiles one by one, keeping the valid ones.
+fn deserialize_profiles<'de, D>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, Profile>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let profiles: HashMap<String, serde_json::Value> = section_or_default("profiles", value);
+    Ok(profiles
+        .into_iter()
+        .map(|(name, value)| {
+            let profile = section_or_default(&format!("profiles.{}", name), value);
+            (name, profile)
+        })
+        .collect())
+}
+
+impl Urls {
+    /// Build urls for `base`, then apply overrides after validating them.
+    pub fn with_overrides(base: &str, overrides: &UrlOverrides) -> Result<Self> {
+        let base = match overrides.base {
+            Some(ref base) => {
+                let url = Url::parse(base)
+                    .map_err(|e| LeetUpError::Any(anyhow!("Invalid base `{}`: {}", base, e)))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return Err(LeetUpError::Any(anyhow!(
+                        "Invalid base `{}`: expected http(s) URL",
+                        base
+                    )));
+                }
+                base.trim_end_matches('/')
+            }
+            None => base,
+        };
+
+        let mut urls = Urls::new(base);
+        let endpoints: [(&str, &mut String, &Option<String>, &[&str]); 9] = [
+            ("api", &mut urls.api, &overrides.api, &[]),
+            ("graphql", &mut urls.graphql, &overrides.graphql, &[]),
+            ("problems", &mut urls.problems, &overrides.problems, &[]),
+            (
+                "problems_all",
+                &mut urls.problems_all,
+                &overrides.problems_all,
+                &[],
+            ),
+            ("test", &mut urls.test, &overrides.test, &["$slug"]),
+            ("submit", &mut urls.submit, &overrides.submit, &["$slug"]),
+            (
+                "submissions",
+                &mut urls.submissions,
+                &overrides.submissions,
+                &["$slug"],
+            ),
+            (
+                "submission",
+                &mut urls.submission,
+                &overrides.submission,
+                &["$id"],
+            ),
+            ("verify", &mut urls.verify, &overrides.verify, &["$id"]),
+        ];
+
+        for (name, url, template, placeholders) in endpoints {
+            if let Some(template) = template {
+                *url = Urls::endpoint(base, name, template, placeholders)?;
+            }
+        }
+
+        Ok(urls)
+    }
+
+    fn endpoint(base: &str, name: &str, template: &str, placeholders: &[&str]) -> Result<String> {
+        let endpoint = if template.starts_with('/') {
+            format!("{}{}", base, template)
+        } else {
+            template.to_owned()
+        };
+
+        if let Some(placeholder) = placeholders.iter().find(|p| !endpoint.contains(*p)) {
+            return Err(LeetUpError::Any(anyhow!(
+                "Invalid `{}` endpoint `{}`: missing `{}`",
+                name,
+                template,
+                placeholder
+            )));
+        }
+        let sample = endpoint.replace("$slug", "two-sum").replace("$id", "1");
+        Url::parse(&sample).map_err(|e| {
+            LeetUpError::Any(anyhow!("Invalid `{}` endpoint `{}`: {}", name, template, e))
+        })?;
+
+        Ok(endpoint)
+    }
+
+    pub fn new(base: &str) -> Self {
+        Urls {
+            base: base.to_owned(),
+            api: format!("{}/api", base),
+            graphql: format!("{}/graphql", base),
+            problems: format!("{}/problems/", base),
+            problems_all: format!("{}/api/problems/all", base),
+            github_login: format!("{}/accounts/github/login/?next=%2F", base),
+            github_login_request: "https://github.com/login".to_string(),
+            github_session_request: "https://github.com/session".to_string(),
+            test: format!("{}/problems/$slug/interpret_solution/", base),
+            submit: format!("{}/problems/$slug/submit/", base),
+            submissions: format!("{}/api/submissions/$slug", base),
+            submission: format!("{}/submissions/detail/$id", base),
+            verify: format!("{}/submissions/detail/$id/check/", base),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct InjectCode {
     pub before_code: Option<Either>,
@@ -127,6 +634,7 @@ pub struct InjectCode {
 pub struct PickHook {
     working_dir: Option<String>,
     script: Option<PickHookScript>,
+    project_layout: Option<ProjectLayout>,
 }
 
 impl PickHook {
@@ -134,6 +642,10 @@ impl PickHook {
         self.working_dir.as_ref().map(String::as_ref)
     }
 
+    pub fn project_layout(&self) -> Option<ProjectLayout> {
+        self.project_layout
+    }
+
     pub fn script_pre_generation(&self) -> Option<&Either> {
         match self.script.as_ref() {
             Some(script) => script.pre_generation.as_ref(),
@@ -149,6 +661,14 @@ impl PickHook {
     }
 }
 
+/// Project generated around picked problems in `working_dir`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectLayout {
+    /// Cargo package where each problem is a `tests/<slug>.rs` target, Rust only.
+    Cargo,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PickHookScript {
     pre_generation: Option<Either>,
@@ -171,9 +691,9 @@ fn test_config() {
     let file_path = data_dir.path().join("config.json");
 
     let mut file = std::fs::File::create(&file_path).unwrap();
-    file.write(data.to_string().as_bytes()).unwrap();
+    file.write_all(data.to_string().as_bytes()).unwrap();
 
-    let config: Config = Config::get(&file_path);
+    let config: Config = Config::get(&file_path).unwrap();
     assert!(config.inject_code.is_some());
     assert!(!config.urls.base.is_empty());
     assert!(config.pick_hook.is_some());
@@ -181,3 +701,257 @@ fn test_config() {
     drop(file);
     data_dir.close().unwrap();
 }
+
+#[test]
+fn test_config_invalid() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let file_path = data_dir.path().join("config.json");
+
+    // A missing config is not an error
+    let config = Config::get(&file_path).unwrap();
+    assert!(config.session_encryption.is_none());
+
+    std::fs::write(
+        &file_path,
+        r#"{"lang": "rust", "session_encryption": {"keyfile": "~/leetup.key"}}"#,
+    )
+    .unwrap();
+    assert!(Config::get(&file_path).is_err());
+    std::fs::write(&file_path, r#"{"lang": "rust",}"#).unwrap();
+    assert!(Config::get(&file_path).is_err());
+    data_dir.close().unwrap();
+}
+
+#[test]
+fn test_config_judge() {
+    use std::io::Write;
+
+    assert_eq!(JudgeConfig::default().timeout(), Duration::from_secs(120));
+    assert_eq!(
+        RunConfig::default().timeout(),
+        Some(Duration::from_secs(10))
+    );
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let data = serde_json::json!({
+        "lang": "rust",
+        "judge": {
+            "timeout": 30,
+            "max_poll_interval": 100
+        },
+        "run": {
+            "timeout": 0
+        },
+        "http": {
+            "rate_limit": 4
+        }
+    });
+    let file_path = data_dir.path().join("config.json");
+
+    let mut file = std::fs::File::create(&file_path).unwrap();
+    file.write_all(data.to_string().as_bytes()).unwrap();
+
+    let config: Config = Config::get(&file_path).unwrap();
+    assert_eq!(config.judge.timeout(), Duration::from_secs(30));
+    assert_eq!(config.judge.poll_interval(), Duration::from_millis(200));
+    assert_eq!(config.judge.max_poll_interval(), Duration::from_millis(200));
+    assert!(config.run.timeout().is_none());
+    assert_eq!(
+        config.cache.problems_ttl(),
+        Some(Duration::from_secs(24 * 60 * 60))
+    );
+    assert!(!config.cache.is_problem_stale(1_000, 1_000 + 60));
+    assert!(config
+        .cache
+        .is_problem_stale(1_000, 1_000 + 7 * 24 * 60 * 60));
+    assert_eq!(config.http.max_concurrency, 4);
+    assert_eq!(config.http.timeout(), Some(Duration::from_secs(30)));
+    assert_eq!(config.http.retries, 2);
+    assert_eq!(
+        config.http.rate_limit_interval(),
+        Some(Duration::from_millis(250))
+    );
+    drop(file);
+    data_dir.close().unwrap();
+}
+
+#[test]
+fn test_config_site() {
+    use std::io::Write;
+
+    assert_eq!(Config::default().site, Site::LeetcodeCom);
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let data = serde_json::json!({
+        "lang": "rust",
+        "site": "leetcode.cn"
+    });
+    let file_path = data_dir.path().join("config.json");
+
+    let mut file = std::fs::File::create(&file_path).unwrap();
+    file.write_all(data.to_string().as_bytes()).unwrap();
+
+    let mut config: Config = Config::get(&file_path).unwrap();
+    assert_eq!(config.site, Site::LeetcodeCn);
+    assert_eq!(config.urls.graphql, "https://leetcode.cn/graphql");
+    assert_eq!(
+        config.site.cache_dir(data_dir.path()),
+        data_dir.path().join("leetcode.cn")
+    );
+
+    config.set_site(Site::from_str("com").unwrap());
+    assert_eq!(config.urls.problems, "https://leetcode.com/problems/");
+    drop(file);
+    data_dir.close().unwrap();
+}
+
+#[test]
+fn test_config_url_overrides() {
+    use std::io::Write;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let data = serde_json::json!({
+        "lang": "rust",
+        "urls": {
+            "base": "http://127.0.0.1:8080/",
+            "graphql": "/gql",
+            "verify": "http://127.0.0.1:9090/check/$id"
+        }
+    });
+    let file_path = data_dir.path().join("config.json");
+
+    let mut file = std::fs::File::create(&file_path).unwrap();
+    file.write_all(data.to_string().as_bytes()).unwrap();
+
+    let mut config: Config = Config::get(&file_path).unwrap();
+    assert_eq!(config.urls.base, "http://127.0.0.1:8080");
+    assert_eq!(config.urls.graphql, "http://127.0.0.1:8080/gql");
+    assert_eq!(
+        config.urls.problems_all,
+        "http://127.0.0.1:8080/api/problems/all"
+    );
+    assert_eq!(config.urls.verify, "http://127.0.0.1:9090/check/$id");
+
+    // `--site` replaces overridden base only
+    config.set_site(Site::LeetcodeCom);
+    assert_eq!(config.urls.graphql, "https://leetcode.com/gql");
+    assert_eq!(config.urls.verify, "http://127.0.0.1:9090/check/$id");
+    drop(file);
+    data_dir.close().unwrap();
+}
+
+#[test]
+fn test_url_overrides_validation() {
+    let overrides =
+        |json: serde_json::Value| -> UrlOverrides { serde_json::from_value(json).unwrap() };
+    let base = Site::LeetcodeCom.base();
+
+    assert!(
+        Urls::with_overrides(base, &overrides(serde_json::json!({"base": "leetcode"}))).is_err()
+    );
+    assert!(Urls::with_overrides(
+        base,
+        &overrides(serde_json::json!({"base": "ftp://leetcode.com"}))
+    )
+    .is_err());
+    assert!(Urls::with_overrides(
+        base,
+        &overrides(serde_json::json!({"test": "/problems/interpret"}))
+    )
+    .is_err());
+    assert!(
+        Urls::with_overrides(base, &overrides(serde_json::json!({"verify": "check/$id"}))).is_err()
+    );
+    assert!(serde_json::from_value::<UrlOverrides>(serde_json::json!({"grapql": "/gql"})).is_err());
+    assert!(Urls::with_overrides(
+        base,
+        &overrides(serde_json::json!({"submit": "/submit/$slug"}))
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_config_profile() {
+    use std::io::Write;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let data = serde_json::json!({
+        "lang": "rust",
+        "profiles": {
+            "work": {"lang": "java"}
+        }
+    });
+    let file_path = data_dir.path().join("config.json");
+
+    let mut file = std::fs::File::create(&file_path).unwrap();
+    file.write_all(data.to_string().as_bytes()).unwrap();
+
+    let mut config: Config = Config::get(&file_path).unwrap();
+    assert!(config.profile.is_none());
+    config.set_profile("personal").unwrap();
+    assert!(matches!(config.lang, Lang::Rust(..)));
+    config.set_profile("work").unwrap();
+    assert_eq!(config.profile.as_deref(), Some("work"));
+    assert!(matches!(config.lang, Lang::Java(..)));
+    assert!(config.set_profile("../work").is_err());
+    assert!(config.session_encryption.is_none());
+    drop(file);
+    data_dir.close().unwrap();
+}
+
+#[test]
+fn test_config_session_encryption() {
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "lang": "rust",
+        "session_encryption": "passphrase"
+    }))
+    .unwrap();
+    assert_eq!(
+        config.session_encryption,
+        Some(SessionEncryption::Passphrase)
+    );
+
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "lang": "rust",
+        "session_encryption": {"key_file": "/tmp/leetup.key"}
+    }))
+    .unwrap();
+    assert_eq!(
+        config.session_encryption,
+        Some(SessionEncryption::KeyFile(PathBuf::from("/tmp/leetup.key")))
+    );
+}
+
+#[test]
+fn test_config_invalid_sections() {
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "lang": "java",
+        "judge": {"timout": 5},
+        "cache": {"problems_ttl": "1h"},
+        "http": {"retries": 1, "proxies": "http://127.0.0.1:3128"},
+        "profiles": {
+            "work": {"lang": "python3"},
+            "home": {"language": "rust"}
+        },
+        "session_encryption": "passphrase"
+    }))
+    .unwrap();
+
+    // Only the invalid sections fall back to defaults
+    assert!(matches!(config.lang, Lang::Java(..)));
+    assert_eq!(config.judge.timeout(), Duration::from_secs(120));
+    assert_eq!(
+        config.cache.problems_ttl(),
+        Some(Duration::from_secs(24 * 60 * 60))
+    );
+    assert_eq!(config.http.retries, 2);
+    assert!(matches!(
+        config.profiles["work"].lang,
+        Some(Lang::Python3(..))
+    ));
+    assert!(config.profiles["home"].lang.is_none());
+    assert_eq!(
+        config.session_encryption,
+        Some(SessionEncryption::Passphrase)
+    );
+}
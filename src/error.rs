@@ -21,9 +21,30 @@ pub enum LeetUpError {
     /// Reqwest Error
     Reqwest(#[from] reqwest::Error),
 
+    /// SQLite Error, e.g. reading Firefox cookies
+    Sqlite(#[from] rusqlite::Error),
+
     /// Invalid header value error
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
 
+    /// Session Expired Error, the cached session was rejected with 401/403
+    #[error("Session is expired or invalid ({0}), login again using `leetup user -c` or `leetup user --import-cookies`")]
+    SessionExpired(reqwest::StatusCode),
+
+    /// Login Required Error, a request without session was rejected with 401/403
+    #[error(
+        "Login required ({0}), login using `leetup user -c` or `leetup user --import-cookies`"
+    )]
+    LoginRequired(reqwest::StatusCode),
+
+    /// Offline Error, a request was attempted in offline mode
+    #[error("Unable to reach {0} in offline mode, try again without `--offline`")]
+    Offline(String),
+
+    /// Not Cached Error, data required in offline mode is missing from the cache
+    #[error("{0} is not cached, run `leetup sync` before going offline")]
+    NotCached(String),
+
     /// Option None Error
     #[error("Tried to unwrap None")]
     OptNone,
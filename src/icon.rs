@@ -1,3 +1,7 @@
+use std::fmt;
+
+use crate::style;
+
 pub enum Icon {
     Yes,
     _No,
@@ -7,15 +11,38 @@ pub enum Icon {
     Empty,
 }
 
-impl ToString for Icon {
-    fn to_string(&self) -> String {
+impl Icon {
+    /// Fallback when unicode can't be printed, e.g. output is piped.
+    fn ascii(&self) -> &'static str {
         match self {
-            Icon::Yes => "✔".to_string(),
-            Icon::_No => "✘".to_string(),
-            Icon::Star => "★".to_string(),
-            Icon::_Unstar => "☆".to_string(),
-            Icon::Lock => "🔒".to_string(),
-            Icon::Empty => " ".to_string(),
+            Icon::Yes => "v",
+            Icon::_No => "x",
+            Icon::Star => "*",
+            Icon::_Unstar => "-",
+            Icon::Lock => "$",
+            Icon::Empty => " ",
         }
     }
+
+    fn unicode(&self) -> &'static str {
+        match self {
+            Icon::Yes => "✔",
+            Icon::_No => "✘",
+            Icon::Star => "★",
+            Icon::_Unstar => "☆",
+            Icon::Lock => "🔒",
+            Icon::Empty => " ",
+        }
+    }
+}
+
+impl fmt::Display for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let icon = if style::is_enabled() {
+            self.unicode()
+        } else {
+            self.ascii()
+        };
+        write!(f, "{}", icon)
+    }
 }
@@ -10,4 +10,5 @@ pub(crate) mod client;
 pub(crate) mod icon;
 pub(crate) mod model;
 pub(crate) mod service;
+pub(crate) mod style;
 pub(crate) mod template;
@@ -1,8 +1,10 @@
-use leetup::{cmd, Result};
+use leetup::cmd;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     env_logger::init();
-    cmd::process().await?;
-    Ok(())
+    if let Err(e) = cmd::process().await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
 }
@@ -1,13 +1,14 @@
 use std::cmp::Ordering;
+use std::fmt;
 use std::str::FromStr;
 
-use ansi_term::Color::{Green, Red, Yellow};
-use serde::Deserialize;
+use ansi_term::Colour::{Green, Red, Yellow};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use DifficultyType::*;
 
-use crate::{Either, LeetUpError};
+use crate::{style::paint, Either, LeetUpError};
 
 #[derive(Debug)]
 pub struct Problem {
@@ -42,13 +43,14 @@ impl FromStr for DifficultyType {
     }
 }
 
-impl ToString for DifficultyType {
-    fn to_string(&self) -> String {
-        match self {
-            Easy => "Easy".into(),
-            Medium => "Medium".into(),
-            Hard => "Hard".into(),
-        }
+impl fmt::Display for DifficultyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            Easy => "Easy",
+            Medium => "Medium",
+            Hard => "Hard",
+        };
+        write!(f, "{}", level)
     }
 }
 
@@ -59,7 +61,7 @@ pub enum Difficulty {
     String(String),
 }
 
-impl<'a> From<&'_ Difficulty> for DifficultyType {
+impl From<&Difficulty> for DifficultyType {
     fn from(difficulty: &Difficulty) -> Self {
         match difficulty {
             Difficulty::Cardinal { level } => level.clone(),
@@ -68,14 +70,25 @@ impl<'a> From<&'_ Difficulty> for DifficultyType {
     }
 }
 
-impl ToString for Difficulty {
-    fn to_string(&self) -> String {
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let level: DifficultyType = self.into();
-        match level {
-            Easy => Green.paint(Easy.to_string()).to_string(),
-            Medium => Yellow.paint(Medium.to_string()).to_string(),
-            Hard => Red.paint(Hard.to_string()).to_string(),
-        }
+        let colored = match level {
+            Easy => paint(Green, &Easy.to_string()),
+            Medium => paint(Yellow, &Medium.to_string()),
+            Hard => paint(Red, &Hard.to_string()),
+        };
+        write!(f, "{}", colored)
+    }
+}
+
+impl Serialize for Difficulty {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let level: DifficultyType = self.into();
+        serializer.serialize_str(&level.to_string())
     }
 }
 
@@ -84,10 +97,16 @@ pub type ProblemInfoSeq = Vec<Box<dyn ProblemInfo + Send + 'static>>;
 pub trait ProblemInfo {
     fn question_id(&self) -> usize;
     fn question_title(&self) -> &str;
+    fn question_title_slug(&self) -> &str;
     fn difficulty(&self) -> &Difficulty;
     fn is_favorite(&self) -> Option<bool>;
     fn is_paid_only(&self) -> bool;
     fn status(&self) -> Option<&str>;
+
+    /// Whether the problem was solved, attempted problems have the status `notac`.
+    fn is_accepted(&self) -> bool {
+        self.status() == Some("ac")
+    }
 }
 
 impl PartialEq<Self> for dyn ProblemInfo + '_ + Send {
@@ -132,27 +151,13 @@ impl Ord for dyn ProblemInfo {
 
 #[derive(Deserialize, Debug)]
 pub struct Stat {
-    pub question_id: usize,
-
-    #[serde(rename = "question__article__live")]
-    pub question_article_live: Option<bool>,
-
-    #[serde(rename = "question__article__slug")]
-    pub question_article_slug: Option<String>,
-
     #[serde(rename = "question__title")]
     pub question_title: String,
 
     #[serde(rename = "question__title_slug")]
     pub question_title_slug: String,
 
-    #[serde(rename = "question__hide")]
-    pub question_hide: bool,
-
-    pub total_acs: usize,
-    pub total_submitted: usize,
     pub frontend_question_id: usize,
-    pub is_new_question: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -162,8 +167,6 @@ pub struct StatStatusPair {
     pub difficulty: Difficulty,
     pub paid_only: bool,
     pub is_favor: bool,
-    pub frequency: f64,
-    pub progress: f64,
 }
 
 #[derive(Deserialize, Debug)]
@@ -191,21 +194,194 @@ pub struct ListResponse {
     pub ac_medium: usize,
     pub ac_hard: usize,
     pub stat_status_pairs: Vec<StatStatusPair>,
-    pub frequency_high: usize,
-    pub frequency_mid: usize,
-    pub category_slug: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TopicTag {
+    pub name: String,
+    pub slug: String,
+}
+
+/// Question detail returned by `getQuestionDetail` query.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionDetail {
+    pub question_frontend_id: Option<String>,
+    pub title: Option<String>,
+    pub title_slug: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub content: Option<String>,
+    pub stats: Option<String>,
+    pub likes: Option<u32>,
+    pub dislikes: Option<u32>,
+    pub code_definition: Option<String>,
+    pub sample_test_case: Option<String>,
+    pub example_testcases: Option<String>,
+    pub meta_data: Option<String>,
+    pub translated_content: Option<String>,
+
+    #[serde(default)]
+    pub topic_tags: Vec<TopicTag>,
+
+    #[serde(default)]
+    pub hints: Vec<String>,
+}
+
+impl QuestionDetail {
+    pub fn stats(&self) -> Option<QuestionStats> {
+        self.stats
+            .as_ref()
+            .and_then(|stats| serde_json::from_str(stats).ok())
+    }
+
+    pub fn meta_data(&self) -> Option<MetaData> {
+        self.meta_data
+            .as_ref()
+            .and_then(|meta_data| serde_json::from_str(meta_data).ok())
+    }
+}
+
+/// Daily challenge cached by `CacheKey::Daily`, overwritten once the date changes.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CachedDaily {
+    /// UTC date of the challenge.
+    pub date: String,
+    pub slug: String,
+}
+
+/// Response of `getQuestionDetail` cached per slug by `CacheKey::Problem`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CachedQuestion {
+    /// Version of the query the response was fetched with.
+    pub version: String,
+    /// UNIX timestamp of the fetch.
+    pub fetched_at: u64,
+    pub response: serde_json::Value,
+}
+
+/// Signature of a solution, serialized as a JSON string in `QuestionDetail`.
+///
+/// Design problems have no function `name`, only a class.
+#[derive(Deserialize, Debug, Clone)]
+pub struct MetaData {
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub params: Vec<MetaParam>,
+
+    #[serde(rename = "return")]
+    pub return_type: Option<MetaReturn>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MetaParam {
+    pub name: String,
+
+    #[serde(rename = "type")]
+    pub param_type: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MetaReturn {
+    #[serde(rename = "type")]
+    pub return_type: String,
+}
+
+/// Acceptance stats of a question, serialized as a JSON string in `QuestionDetail`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionStats {
+    pub total_accepted: String,
+    pub total_submission: String,
+    pub ac_rate: String,
+}
+
+/// Count problems of a list by their status.
+#[derive(Default, Debug)]
+pub struct StatCounter {
+    pub total: usize,
+    pub accepted: usize,
+    pub locked: usize,
+    pub starred: usize,
+}
+
+impl StatCounter {
+    pub fn add(&mut self, prob: &(dyn ProblemInfo + Send)) {
+        self.total += 1;
+        if prob.is_accepted() {
+            self.accepted += 1;
+        }
+        if prob.is_paid_only() {
+            self.locked += 1;
+        }
+        if prob.is_favorite().unwrap_or_default() {
+            self.starred += 1;
+        }
+    }
+
+    pub fn not_accepted(&self) -> usize {
+        self.total - self.accepted
+    }
+
+    /// Percentage of accepted problems in the counter.
+    pub fn solved_percentage(&self) -> f64 {
+        percentage(self.accepted, self.total)
+    }
+}
+
+pub fn percentage(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / whole as f64
+}
+
+/// Status of the user of a session returned by `globalData` query.
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UserStatus {
+    #[serde(default)]
+    pub is_signed_in: bool,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub is_premium: bool,
+}
+
+/// A submission of a problem listed by `/api/submissions/$slug`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Submission {
+    pub id: u64,
+    pub lang: String,
+    pub time: String,
+    pub timestamp: u64,
+    pub status_display: String,
+    pub runtime: String,
+    pub memory: Option<String>,
+    pub code: Option<String>,
+    pub title_slug: Option<String>,
+}
+
+impl Submission {
+    pub fn is_accepted(&self) -> bool {
+        self.status_display == "Accepted"
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SubmissionList {
+    pub submissions_dump: Vec<Submission>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct CodeDefinition {
     pub value: String,
-    pub text: String,
 
     #[serde(rename = "defaultCode")]
     pub default_code: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct SubmissionResponse {
     pub state: Option<String>,
     pub input: Option<Either>,
@@ -237,6 +413,36 @@ pub struct SubmissionResponse {
     pub total_testcases: Option<u32>,
 }
 
+/// Outcome of a test run or submission, computed from the judge's response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer,
+    CompileError,
+    RuntimeError,
+    TimeLimitExceeded,
+    MemoryLimitExceeded,
+    OutputLimitExceeded,
+}
+
+impl SubmissionResponse {
+    pub fn verdict(&self) -> Verdict {
+        if self.has_compile_error() {
+            return Verdict::CompileError;
+        }
+        match self.status_msg.to_lowercase().as_str() {
+            "time limit exceeded" => Verdict::TimeLimitExceeded,
+            "memory limit exceeded" => Verdict::MemoryLimitExceeded,
+            "output limit exceeded" => Verdict::OutputLimitExceeded,
+            _ if self.has_runtime_error() => Verdict::RuntimeError,
+            // Test runs are "Accepted" by the judge even if the answer is not the expected one
+            _ if self.correct_answer == Some(false) || self.has_error() => Verdict::WrongAnswer,
+            _ => Verdict::Accepted,
+        }
+    }
+}
+
 pub trait ExecutionErrorResponse {
     fn has_compile_error(&self) -> bool;
 
@@ -267,6 +473,7 @@ impl ExecutionErrorResponse for SubmissionResponse {
 }
 
 impl ProblemInfo for StatStatusPair {
+    #[allow(clippy::misnamed_getters)]
     fn question_id(&self) -> usize {
         self.stat.frontend_question_id
     }
@@ -275,6 +482,10 @@ impl ProblemInfo for StatStatusPair {
         self.stat.question_title.as_str()
     }
 
+    fn question_title_slug(&self) -> &str {
+        self.stat.question_title_slug.as_str()
+    }
+
     fn difficulty(&self) -> &Difficulty {
         &self.difficulty
     }
@@ -303,6 +514,10 @@ impl ProblemInfo for TopicTagQuestion {
         self.title.as_str()
     }
 
+    fn question_title_slug(&self) -> &str {
+        self.title_slug.as_str()
+    }
+
     fn difficulty(&self) -> &Difficulty {
         &self.difficulty
     }
@@ -319,3 +534,74 @@ impl ProblemInfo for TopicTagQuestion {
         self.status.as_ref().map(String::as_ref)
     }
 }
+
+#[test]
+fn test_stat_counter() {
+    let json = serde_json::json!([
+        {"status": "ac", "difficulty": "Easy", "title": "Two Sum", "isPaidOnly": false, "titleSlug": "two-sum", "questionFrontendId": "1"},
+        {"status": null, "difficulty": "Easy", "title": "Two Sum II", "isPaidOnly": true, "titleSlug": "two-sum-ii", "questionFrontendId": "167"},
+        {"status": "notac", "difficulty": "Hard", "title": "Median of Two Sorted Arrays", "isPaidOnly": false, "titleSlug": "median-of-two-sorted-arrays", "questionFrontendId": "4"}
+    ]);
+    let probs: Vec<TopicTagQuestion> = serde_json::from_value(json).unwrap();
+
+    let mut counter = StatCounter::default();
+    for prob in &probs {
+        counter.add(prob);
+    }
+
+    assert_eq!(counter.total, 3);
+    assert_eq!(counter.accepted, 1);
+    assert_eq!(counter.not_accepted(), 2);
+    assert_eq!(counter.locked, 1);
+    assert_eq!(counter.starred, 0);
+    assert!((counter.solved_percentage() - 100.0 / 3.0).abs() < f64::EPSILON);
+    assert_eq!(StatCounter::default().solved_percentage(), 0.0);
+}
+
+#[test]
+fn test_verdict() {
+    let response = |status_msg: &str, extra: serde_json::Value| -> SubmissionResponse {
+        let mut json = serde_json::json!({
+            "lang": "rust",
+            "pretty_lang": "Rust",
+            "run_success": true,
+            "status_memory": "2.1 MB",
+            "status_msg": status_msg,
+            "status_runtime": "0 ms",
+            "submission_id": "1",
+            "total_correct": 63,
+            "total_testcases": 63
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    };
+
+    let accepted = response("Accepted", serde_json::json!({}));
+    assert_eq!(accepted.verdict(), Verdict::Accepted);
+    assert_eq!(
+        serde_json::to_value(accepted.verdict()).unwrap(),
+        "accepted"
+    );
+    assert_eq!(
+        response("Accepted", serde_json::json!({"correct_answer": false})).verdict(),
+        Verdict::WrongAnswer
+    );
+    assert_eq!(
+        response("Wrong Answer", serde_json::json!({"total_correct": 12})).verdict(),
+        Verdict::WrongAnswer
+    );
+    assert_eq!(
+        response("Time Limit Exceeded", serde_json::json!({})).verdict(),
+        Verdict::TimeLimitExceeded
+    );
+    assert_eq!(
+        response(
+            "Compile Error",
+            serde_json::json!({"compile_error": "Line 1: error"})
+        )
+        .verdict(),
+        Verdict::CompileError
+    );
+}
@@ -0,0 +1,124 @@
+use ansi_term::Colour::Red;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::printer::{Printer, NEW_LINE};
+use crate::service::utc_date;
+use crate::style::paint;
+
+/// An entry of the cache, listed by `leetup cache stats`.
+#[derive(Debug, Serialize)]
+pub struct CacheEntry {
+    pub key: String,
+    /// Size in bytes of the entry in the cache files.
+    pub size: u64,
+    /// UNIX timestamp after which the entry expires, never if `None`.
+    pub expires_at: Option<u64>,
+    pub expired: bool,
+}
+
+/// Print the entries of the cache along with the size of its files.
+#[derive(Debug)]
+pub struct CacheStatsResult {
+    entries: Vec<CacheEntry>,
+    log_size: u64,
+    stale_size: u64,
+}
+
+impl Printer for CacheStatsResult {
+    fn is_error(&self) -> bool {
+        false
+    }
+
+    fn buffer(&self) -> String {
+        let mut buffer = format!("{:32} {:>10} {}\n", "Key", "Size", "Expires");
+        for entry in &self.entries {
+            let expires = if entry.expired {
+                paint(Red, "Expired")
+            } else {
+                entry.expires_at.map_or("Never".into(), utc_date)
+            };
+            buffer.push_str(&format!(
+                "{:32} {:>10} {}\n",
+                entry.key,
+                human_size(entry.size),
+                expires
+            ));
+        }
+        buffer.push_str(NEW_LINE);
+        buffer.push_str(&format!(
+            "Entries: {}, Files: {}, Stale: {}\n",
+            self.entries.len(),
+            human_size(self.log_size),
+            human_size(self.stale_size)
+        ));
+
+        buffer
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "entries": self.entries,
+            "log_size": self.log_size,
+            "stale_size": self.stale_size,
+        })
+    }
+}
+
+impl CacheStatsResult {
+    pub fn new(entries: Vec<CacheEntry>, log_size: u64, stale_size: u64) -> Self {
+        Self {
+            entries,
+            log_size,
+            stale_size,
+        }
+    }
+}
+
+/// Format a size in bytes, e.g. `1.5 KiB`.
+pub fn human_size(size: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", size, units[0])
+    } else {
+        format!("{:.1} {}", value, units[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{human_size, CacheEntry, CacheStatsResult, Printer};
+
+    #[test]
+    fn print_cache_stats() {
+        let entries = vec![
+            CacheEntry {
+                key: "problems".into(),
+                size: 1536,
+                expires_at: Some(1_798_761_599),
+                expired: false,
+            },
+            CacheEntry {
+                key: "session".into(),
+                size: 80,
+                expires_at: None,
+                expired: false,
+            },
+        ];
+        let result = CacheStatsResult::new(entries, 2048, 0);
+        let buffer = result.buffer();
+        assert!(buffer.contains("problems"));
+        assert!(buffer.contains("1.5 KiB 2026-12-31"));
+        assert!(buffer.contains("80 B Never"));
+        assert!(buffer.contains("Entries: 2, Files: 2.0 KiB, Stale: 0 B"));
+        assert_eq!(result.json()["entries"][0]["size"], 1536);
+        assert_eq!(human_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,46 @@
+use serde_json::Value;
+
+use crate::printer::{Printer, NEW_LINE};
+
+/// Print the outcome of a command that has no richer result, e.g. `cache clear`.
+///
+/// `text`, already styled by the caller, is printed for text output and `json` for json output.
+#[derive(Debug)]
+pub struct MessageResult {
+    text: String,
+    json: Value,
+}
+
+impl Printer for MessageResult {
+    fn is_error(&self) -> bool {
+        false
+    }
+
+    fn buffer(&self) -> String {
+        self.text.to_owned() + NEW_LINE
+    }
+
+    fn json(&self) -> Value {
+        self.json.clone()
+    }
+}
+
+impl MessageResult {
+    pub fn new(text: String, json: Value) -> Self {
+        Self { text, json }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageResult, Printer};
+    use serde_json::json;
+
+    #[test]
+    fn print_message() {
+        let result = MessageResult::new("Removed 2 entries".to_owned(), json!({"removed": 2}));
+        assert!(!result.is_error());
+        assert_eq!(result.buffer(), "Removed 2 entries\n");
+        assert_eq!(result.json(), json!({"removed": 2}));
+    }
+}
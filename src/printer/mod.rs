@@ -1,7 +1,22 @@
+mod cache_stats_printer;
+mod message_printer;
+#[allow(clippy::module_inception)]
 mod printer;
+mod problem_list_printer;
+mod question_printer;
+mod run_printer;
+mod submission_list_printer;
 mod submit_execution_printer;
 mod test_execution_printer;
+mod user_status_printer;
 
+pub use cache_stats_printer::{human_size, CacheEntry, CacheStatsResult};
+pub use message_printer::MessageResult;
 pub use printer::*;
+pub use problem_list_printer::ProblemListResult;
+pub use question_printer::QuestionDetailResult;
+pub use run_printer::LocalRunResult;
+pub use submission_list_printer::SubmissionListResult;
 pub use submit_execution_printer::SubmitExecutionResult;
 pub use test_execution_printer::TestExecutionResult;
+pub use user_status_printer::UserStatusResult;
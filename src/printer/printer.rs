@@ -1,18 +1,73 @@
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde_json::Value;
+
 use crate::model::SubmissionResponse;
+use crate::LeetUpError;
+
+pub(crate) const NEW_LINE: &str = "\n";
+
+/// Format of command results, from `--output`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Colored text for the terminal.
+    #[default]
+    Text,
+
+    /// Text without ANSI escape codes.
+    Plain,
+
+    /// JSON document for scripts and editor plugins.
+    Json,
+}
 
-pub(crate) const NEW_LINE: &'static str = "\n";
-pub(crate) const TEXT_BOLD_ON: &'static str = "\x1b[1m";
-pub(crate) const TEXT_BOLD_OFF: &'static str = "\x1b[m";
+impl OutputFormat {
+    /// Whether progress like spinners can be shown along with the result.
+    pub fn is_interactive(&self) -> bool {
+        *self == OutputFormat::Text && std::io::stdout().is_terminal()
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = LeetUpError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "plain" => Ok(OutputFormat::Plain),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(LeetUpError::Any(anyhow!(
+                "Output format not supported, expected text, plain or json"
+            ))),
+        }
+    }
+}
 
 pub trait Printer {
     fn print(&self) {
         print!("{}", self.buffer());
     }
 
+    /// Print the result in the given `output` format, styling is already disabled for `Plain`.
+    fn print_as(&self, output: OutputFormat) {
+        match output {
+            OutputFormat::Text | OutputFormat::Plain => self.print(),
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&self.json()).unwrap_or_default()
+            ),
+        }
+    }
+
     fn is_error(&self) -> bool;
 
     fn buffer(&self) -> String;
 
+    /// Structured result for `--output json`.
+    fn json(&self) -> Value;
+
     fn total_cases_ratio_buffer(&self, response: &SubmissionResponse) -> String {
         format!(
             "{}/{}",
@@ -20,12 +75,51 @@ pub trait Printer {
             response.total_testcases.unwrap_or(0)
         )
     }
+
+    /// Response of the judge along with its computed `verdict`.
+    fn submission_json(&self, response: &SubmissionResponse) -> Value {
+        let mut json = serde_json::to_value(response).unwrap_or_default();
+        json["verdict"] = serde_json::to_value(response.verdict()).unwrap_or_default();
+        json
+    }
 }
 
 pub mod decorator {
-    use super::*;
+    use ansi_term::Style;
+    use regex::Regex;
+
+    use crate::style;
 
     pub fn bold_text(s: &str) -> String {
-        format!("{}{}{}", s, TEXT_BOLD_ON, TEXT_BOLD_OFF)
+        style::paint(Style::new().bold(), s)
     }
+
+    /// Rewrite HTML tags that have no text equivalent so they survive
+    /// the conversion to text, e.g. `10<sup>5</sup>` becomes `10^5`.
+    pub fn normalize_html(html: &str) -> String {
+        let sup = Regex::new(r"(?s)<sup>(.*?)</sup>").unwrap();
+        let sub = Regex::new(r"(?s)<sub>(.*?)</sub>").unwrap();
+        let html = sup.replace_all(html, "^$1");
+        sub.replace_all(&html, "_$1").into_owned()
+    }
+}
+
+#[test]
+fn test_normalize_html() {
+    let html = "<code>1 &lt;= n &lt;= 10<sup>5</sup></code> and x<sub>i</sub>";
+    assert_eq!(
+        decorator::normalize_html(html),
+        "<code>1 &lt;= n &lt;= 10^5</code> and x_i"
+    );
+}
+
+#[test]
+fn test_output_format() {
+    assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+    assert!(OutputFormat::from_str("yaml").is_err());
+    assert!(!OutputFormat::Plain.is_interactive());
+
+    // Styling is process global, set it rather than relying on the default
+    crate::style::init(crate::style::ColorChoice::Always);
+    assert_eq!(decorator::bold_text("Accepted"), "\x1b[1mAccepted\x1b[0m");
 }
@@ -0,0 +1,191 @@
+use ansi_term::Colour::{Green, Red, Yellow};
+use serde_json::{json, Value};
+
+use crate::icon::Icon;
+use crate::model::DifficultyType::{self, Easy, Hard, Medium};
+use crate::model::{percentage, ListResponse, ProblemInfo, StatCounter};
+use crate::printer::{Printer, NEW_LINE};
+use crate::style::paint;
+
+/// Print a list of problems, along with statistics of the list if `stat` is set.
+pub struct ProblemListResult<'a> {
+    probs: &'a [Box<dyn ProblemInfo + Send>],
+    stat: Option<&'a ListResponse>,
+}
+
+impl Printer for ProblemListResult<'_> {
+    fn is_error(&self) -> bool {
+        self.probs.is_empty()
+    }
+
+    fn buffer(&self) -> String {
+        let mut buffer = self.list_buffer();
+        if let Some(list_response) = self.stat {
+            buffer.push_str(&self.stat_buffer(list_response));
+        }
+
+        buffer
+    }
+
+    fn json(&self) -> Value {
+        self.probs
+            .iter()
+            .map(|prob| {
+                json!({
+                    "id": prob.question_id(),
+                    "title": prob.question_title(),
+                    "slug": prob.question_title_slug(),
+                    "difficulty": prob.difficulty(),
+                    "paid_only": prob.is_paid_only(),
+                    "favorite": prob.is_favorite(),
+                    "status": prob.status(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl<'a> ProblemListResult<'a> {
+    pub fn new(probs: &'a [Box<dyn ProblemInfo + Send>], stat: Option<&'a ListResponse>) -> Self {
+        Self { probs, stat }
+    }
+
+    fn list_buffer(&self) -> String {
+        let mut buffer = String::new();
+        for prob in self.probs {
+            let is_favorite = prob.is_favorite().unwrap_or_default();
+            let starred_icon = if is_favorite {
+                paint(Yellow, &Icon::Star.to_string())
+            } else {
+                Icon::Empty.to_string()
+            };
+
+            let locked_icon = if prob.is_paid_only() {
+                paint(Red, &Icon::Lock.to_string())
+            } else {
+                Icon::Empty.to_string()
+            };
+
+            let acd = if prob.is_accepted() {
+                paint(Green, &Icon::Yes.to_string())
+            } else {
+                Icon::Empty.to_string()
+            };
+
+            buffer.push_str(&format!(
+                "{} {:2} {} [{:^4}] {:75} {:6}",
+                starred_icon,
+                locked_icon,
+                acd,
+                prob.question_id(),
+                prob.question_title(),
+                prob.difficulty().to_string()
+            ));
+            buffer.push_str(NEW_LINE);
+        }
+
+        buffer
+    }
+
+    /// Statistics of the list of problems along with the user's progress.
+    fn stat_buffer(&self, list_response: &ListResponse) -> String {
+        let levels = [Easy, Medium, Hard];
+        let paint_level = |level: &DifficultyType, label: String| match level {
+            Easy => paint(Green, &label),
+            Medium => paint(Yellow, &label),
+            Hard => paint(Red, &label),
+        };
+
+        let mut all = StatCounter::default();
+        let mut counters: Vec<StatCounter> = levels.iter().map(|_| Default::default()).collect();
+        for prob in self.probs {
+            let level: DifficultyType = prob.difficulty().into();
+            counters[level as usize - 1].add(prob.as_ref());
+            all.add(prob.as_ref());
+        }
+
+        let mut buffer = format!(
+            "\n{:8} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}\n",
+            "", "Total", "AC", "Not AC", "Locked", "Starred", "Solved"
+        );
+        let rows = levels.iter().map(Some).zip(counters.iter());
+        for (level, counter) in rows.chain(std::iter::once((None, &all))) {
+            let label = format!(
+                "{:8}",
+                level.map_or("All".into(), DifficultyType::to_string)
+            );
+            buffer.push_str(&format!(
+                "{} {:>8} {:>8} {:>8} {:>8} {:>8} {:>7.2}%\n",
+                level.map_or(label.clone(), |level| paint_level(level, label)),
+                counter.total,
+                counter.accepted,
+                counter.not_accepted(),
+                counter.locked,
+                counter.starred,
+                counter.solved_percentage()
+            ));
+        }
+
+        // Progress of the logged in user across all problems.
+        let mut level_totals = [0; 3];
+        for pair in &list_response.stat_status_pairs {
+            let level: DifficultyType = pair.difficulty().into();
+            level_totals[level as usize - 1] += 1;
+        }
+        let solved = [
+            list_response.ac_easy,
+            list_response.ac_medium,
+            list_response.ac_hard,
+        ];
+
+        buffer.push_str(&format!("\nProgress: {}\n", list_response.user_name));
+        for ((level, ac), total) in levels.iter().zip(solved).zip(level_totals) {
+            buffer.push_str(&format!(
+                "{} {:>8} {:>7.2}%\n",
+                paint_level(level, format!("{:8}", level.to_string())),
+                format!("{}/{}", ac, total),
+                percentage(ac, total)
+            ));
+        }
+        buffer.push_str(&format!(
+            "{:8} {:>8} {:>7.2}%\n",
+            "All",
+            format!("{}/{}", list_response.num_solved, list_response.num_total),
+            percentage(list_response.num_solved, list_response.num_total)
+        ));
+
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Printer, ProblemListResult};
+    use crate::model::{ProblemInfo, TopicTagQuestion};
+
+    #[test]
+    fn print_problem_list() {
+        let json_value = serde_json::json!([
+            {"status": "ac", "difficulty": "Easy", "title": "Two Sum", "isPaidOnly": false, "titleSlug": "two-sum", "questionFrontendId": "1"},
+            {"status": "notac", "difficulty": "Hard", "title": "Median of Two Sorted Arrays", "isPaidOnly": true, "titleSlug": "median-of-two-sorted-arrays", "questionFrontendId": "4"}
+        ]);
+        let probs: Vec<TopicTagQuestion> = serde_json::from_value(json_value).unwrap();
+        let probs: Vec<Box<dyn ProblemInfo + Send>> = probs
+            .into_iter()
+            .map(|prob| Box::new(prob) as Box<dyn ProblemInfo + Send>)
+            .collect();
+
+        let result = ProblemListResult::new(&probs, None);
+        let buffer = String::from_utf8(strip_ansi_escapes::strip(result.buffer())).unwrap();
+        assert!(buffer.contains("✔ [ 1  ] Two Sum"));
+        // Attempted but not accepted
+        assert!(!buffer.lines().nth(1).unwrap().contains('✔'));
+        assert_eq!(buffer.lines().count(), 2);
+
+        let json = result.json();
+        assert_eq!(json[1]["slug"], "median-of-two-sorted-arrays");
+        assert_eq!(json[1]["difficulty"], "Hard");
+        assert_eq!(json[1]["paid_only"], true);
+        assert_eq!(json[0]["status"], "ac");
+    }
+}
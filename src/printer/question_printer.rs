@@ -0,0 +1,270 @@
+use ansi_term::Colour::{Blue, Cyan, Red, Yellow};
+use ansi_term::Style;
+use html2text::render::text_renderer::{RichAnnotation, TaggedLine, TextDecorator};
+use serde_json::Value;
+
+use crate::model::QuestionDetail;
+use crate::printer::{
+    decorator::{bold_text, normalize_html},
+    Printer, NEW_LINE,
+};
+use crate::style::paint;
+
+const CONTENT_WIDTH: usize = 80;
+
+/// Render a question's detail in the terminal.
+#[derive(Debug)]
+pub struct QuestionDetailResult {
+    link: String,
+    question: QuestionDetail,
+}
+
+impl Printer for QuestionDetailResult {
+    fn is_error(&self) -> bool {
+        self.question.content.is_none()
+    }
+
+    fn buffer(&self) -> String {
+        let mut buffer = self.header_buffer();
+        buffer.push_str(NEW_LINE);
+        if self.is_error() {
+            buffer.push_str(&paint(
+                Red,
+                "Problem content is not available, it might be locked!",
+            ));
+            buffer.push_str(NEW_LINE);
+        } else {
+            buffer.push_str(&self.content_buffer());
+            buffer.push_str(&self.hints_buffer());
+        }
+
+        buffer
+    }
+
+    fn json(&self) -> Value {
+        let mut json = serde_json::to_value(&self.question).unwrap_or_default();
+        json["link"] = Value::String(self.link.to_owned());
+        json
+    }
+}
+
+impl QuestionDetailResult {
+    pub fn new(link: String, question: QuestionDetail) -> Self {
+        Self { link, question }
+    }
+
+    fn header_buffer(&self) -> String {
+        let question = &self.question;
+        let mut buffer = String::new();
+
+        let title = format!(
+            "[{}] {}",
+            question.question_frontend_id.as_deref().unwrap_or("-"),
+            question.title.as_deref().unwrap_or_default()
+        );
+        buffer.push_str(&bold_text(&title));
+        if let Some(ref difficulty) = question.difficulty {
+            buffer.push_str(&format!("  {}", difficulty));
+        }
+        buffer.push_str(NEW_LINE);
+        buffer.push_str(&paint(Blue.underline(), &self.link));
+        buffer.push_str(NEW_LINE);
+
+        let mut metas = vec![
+            format!("Likes: {}", question.likes.unwrap_or(0)),
+            format!("Dislikes: {}", question.dislikes.unwrap_or(0)),
+        ];
+        if let Some(stats) = question.stats() {
+            metas.push(format!(
+                "Acceptance: {} ({}/{})",
+                stats.ac_rate, stats.total_accepted, stats.total_submission
+            ));
+        }
+        buffer.push_str(&metas.join(" | "));
+        buffer.push_str(NEW_LINE);
+
+        if !question.topic_tags.is_empty() {
+            let tags = question
+                .topic_tags
+                .iter()
+                .map(|tag| paint(Cyan, &tag.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            buffer.push_str(&format!("Tags: {}", tags));
+            buffer.push_str(NEW_LINE);
+        }
+
+        buffer
+    }
+
+    fn content_buffer(&self) -> String {
+        render_html(self.question.content.as_deref().unwrap_or_default())
+    }
+
+    fn hints_buffer(&self) -> String {
+        let mut buffer = String::new();
+        for (i, hint) in self.question.hints.iter().enumerate() {
+            buffer.push_str(NEW_LINE);
+            buffer.push_str(&bold_text(&format!("Hint {}:", i + 1)));
+            buffer.push_str(NEW_LINE);
+            buffer.push_str(&render_html(hint));
+        }
+        if !buffer.is_empty() {
+            buffer.push_str(NEW_LINE);
+        }
+
+        buffer
+    }
+}
+
+fn render_html(html: &str) -> String {
+    html2text::parse(normalize_html(html).as_bytes())
+        .render(CONTENT_WIDTH, TerminalDecorator)
+        .into_lines()
+        .iter()
+        .map(styled_line)
+        .collect::<Vec<_>>()
+        .join(NEW_LINE)
+}
+
+fn styled_line(line: &TaggedLine<Vec<RichAnnotation>>) -> String {
+    line.tagged_strings()
+        .map(|ts| {
+            let style = ts
+                .tag
+                .iter()
+                .fold(Style::new(), |style, annotation| match annotation {
+                    RichAnnotation::Strong => style.bold(),
+                    RichAnnotation::Emphasis => style.italic(),
+                    RichAnnotation::Strikeout => style.strikethrough(),
+                    RichAnnotation::Link(_) => style.underline(),
+                    RichAnnotation::Code => style.fg(Yellow),
+                    RichAnnotation::Preformat(_) => style.dimmed(),
+                    _ => style,
+                });
+            paint(style, &ts.s)
+        })
+        .collect()
+}
+
+/// Decorate HTML elements with annotations only, styles are applied later
+/// using ANSI escape codes instead of markdown-like markers.
+#[derive(Clone)]
+struct TerminalDecorator;
+
+impl TextDecorator for TerminalDecorator {
+    type Annotation = RichAnnotation;
+
+    fn decorate_link_start(&mut self, url: &str) -> (String, Self::Annotation) {
+        ("".into(), RichAnnotation::Link(url.into()))
+    }
+
+    fn decorate_link_end(&mut self) -> String {
+        "".into()
+    }
+
+    fn decorate_em_start(&mut self) -> (String, Self::Annotation) {
+        ("".into(), RichAnnotation::Emphasis)
+    }
+
+    fn decorate_em_end(&mut self) -> String {
+        "".into()
+    }
+
+    fn decorate_strong_start(&mut self) -> (String, Self::Annotation) {
+        ("".into(), RichAnnotation::Strong)
+    }
+
+    fn decorate_strong_end(&mut self) -> String {
+        "".into()
+    }
+
+    fn decorate_strikeout_start(&mut self) -> (String, Self::Annotation) {
+        ("".into(), RichAnnotation::Strikeout)
+    }
+
+    fn decorate_strikeout_end(&mut self) -> String {
+        "".into()
+    }
+
+    fn decorate_code_start(&mut self) -> (String, Self::Annotation) {
+        ("".into(), RichAnnotation::Code)
+    }
+
+    fn decorate_code_end(&mut self) -> String {
+        "".into()
+    }
+
+    fn decorate_preformat_first(&mut self) -> Self::Annotation {
+        RichAnnotation::Preformat(false)
+    }
+
+    fn decorate_preformat_cont(&mut self) -> Self::Annotation {
+        RichAnnotation::Preformat(true)
+    }
+
+    fn decorate_image(&mut self, src: &str, title: &str) -> (String, Self::Annotation) {
+        (title.into(), RichAnnotation::Image(src.into()))
+    }
+
+    fn header_prefix(&mut self, level: usize) -> String {
+        "#".repeat(level) + " "
+    }
+
+    fn quote_prefix(&mut self) -> String {
+        "> ".into()
+    }
+
+    fn unordered_item_prefix(&mut self) -> String {
+        "• ".into()
+    }
+
+    fn ordered_item_prefix(&mut self, i: i64) -> String {
+        format!("{}. ", i)
+    }
+
+    fn make_subblock_decorator(&self) -> Self {
+        TerminalDecorator
+    }
+
+    fn finalise(&mut self, _links: Vec<String>) -> Vec<TaggedLine<Self::Annotation>> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Printer, QuestionDetailResult};
+    use crate::model::QuestionDetail;
+    use serde_json::from_value;
+
+    #[test]
+    fn print_question_detail() {
+        let json_value = serde_json::json!({
+            "questionFrontendId": "1",
+            "title": "Two Sum",
+            "titleSlug": "two-sum",
+            "difficulty": "Easy",
+            "likes": 52000,
+            "dislikes": 1700,
+            "stats": "{\"totalAccepted\": \"12.6M\", \"totalSubmission\": \"24.9M\", \"totalAcceptedRaw\": 12600000, \"totalSubmissionRaw\": 24900000, \"acRate\": \"50.6%\"}",
+            "topicTags": [{"name": "Array", "slug": "array"}, {"name": "Hash Table", "slug": "hash-table"}],
+            "content": "<p>Given an array of integers <code>nums</code>, return <strong>indices</strong>.</p><ul><li><code>2 &lt;= nums.length &lt;= 10<sup>4</sup></code></li></ul>",
+            "hints": ["Try a <b>hash map</b>."]
+        });
+        let question = from_value::<QuestionDetail>(json_value).unwrap();
+
+        let result = QuestionDetailResult::new(
+            "https://leetcode.com/problems/two-sum/".to_owned(),
+            question,
+        );
+        let buffer = String::from_utf8(strip_ansi_escapes::strip(result.buffer())).unwrap();
+
+        assert!(buffer.contains("[1] Two Sum  Easy"));
+        assert!(buffer.contains("Likes: 52000 | Dislikes: 1700 | Acceptance: 50.6% (12.6M/24.9M)"));
+        assert!(buffer.contains("Tags: Array, Hash Table"));
+        assert!(buffer.contains("return indices."));
+        assert!(buffer.contains("• 2 <= nums.length <= 10^4"));
+        assert!(buffer.contains("Hint 1:\nTry a hash map."));
+    }
+}
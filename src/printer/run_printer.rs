@@ -0,0 +1,98 @@
+use ansi_term::Colour::Red;
+use serde_json::Value;
+
+use crate::icon::Icon;
+use crate::printer::{Printer, NEW_LINE};
+use crate::service::LocalRun;
+use crate::style::paint;
+
+/// Print the result of running a solution locally.
+#[derive(Debug)]
+pub struct LocalRunResult {
+    run: LocalRun,
+}
+
+impl Printer for LocalRunResult {
+    fn is_error(&self) -> bool {
+        self.run.error.is_some()
+    }
+
+    fn buffer(&self) -> String {
+        let mut buffer = String::new();
+        for (i, case) in self.run.cases.iter().enumerate() {
+            let header = format!("Case {}:\n", i + 1);
+            match case.output {
+                Some(ref output) => {
+                    // Outputs are not compared against expected ones, only failed cases are marked
+                    buffer.push_str(&format!("{} {}", Icon::Empty, header));
+                    buffer.push_str(&format!("\tInput: \n\t\t{}\n", case.input.join("\n\t\t")));
+                    buffer.push_str(&format!("\n\tOutput: {}\n", output));
+                }
+                None => {
+                    buffer.push_str(&paint(Red, &format!("{} {}", Icon::_No, header)));
+                    buffer.push_str(&format!("\tInput: \n\t\t{}\n", case.input.join("\n\t\t")));
+                }
+            }
+            if !case.stdout.is_empty() {
+                buffer.push_str(&format!(
+                    "\tStdout: \n\t\t{}\n",
+                    case.stdout.trim_end().replace('\n', "\n\t\t")
+                ));
+            }
+            buffer.push_str(NEW_LINE);
+        }
+
+        if let Some(ref error) = self.run.error {
+            buffer.push_str(&paint(Red, &format!("{} {}", Icon::_No, error)));
+            buffer.push_str(NEW_LINE);
+        }
+
+        buffer
+    }
+
+    fn json(&self) -> Value {
+        serde_json::to_value(&self.run).unwrap_or_default()
+    }
+}
+
+impl LocalRunResult {
+    pub fn new(run: LocalRun) -> Self {
+        Self { run }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalRunResult, Printer};
+    use crate::service::{LocalRun, RunCase};
+
+    #[test]
+    fn print_local_run() {
+        let run = LocalRun {
+            cases: vec![
+                RunCase {
+                    input: vec!["[2,7,11,15]".into(), "9".into()],
+                    output: Some("[0,1]".into()),
+                    stdout: "debug\n".into(),
+                },
+                RunCase {
+                    input: vec!["[3,3]".into(), "6".into()],
+                    output: None,
+                    stdout: String::new(),
+                },
+            ],
+            error: Some("Runtime Error\npanicked at 'index out of bounds'".into()),
+        };
+
+        let result = LocalRunResult::new(run);
+        let buffer = String::from_utf8(strip_ansi_escapes::strip(result.buffer())).unwrap();
+
+        assert!(result.is_error());
+        // Tabs are stripped along with escape codes
+        assert!(buffer.contains("  Case 1:\nInput: \n[2,7,11,15]\n9\n\nOutput: [0,1]\n"));
+        assert!(!buffer.contains('✔'));
+        assert!(buffer.contains("Stdout: \ndebug\n"));
+        assert!(buffer.contains("✘ Case 2:"));
+        assert!(buffer.contains("✘ Runtime Error\npanicked"));
+    }
+}
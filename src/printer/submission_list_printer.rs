@@ -0,0 +1,110 @@
+use ansi_term::Colour::{Green, Red};
+use serde_json::Value;
+
+use crate::icon::Icon;
+use crate::model::Submission;
+use crate::printer::{Printer, NEW_LINE};
+use crate::style::paint;
+
+/// Print submission history of a problem.
+#[derive(Debug)]
+pub struct SubmissionListResult<'a> {
+    submissions: &'a [Submission],
+}
+
+impl Printer for SubmissionListResult<'_> {
+    fn is_error(&self) -> bool {
+        self.submissions.is_empty()
+    }
+
+    fn buffer(&self) -> String {
+        if self.is_error() {
+            return "No submissions found!".to_owned() + NEW_LINE;
+        }
+
+        let mut buffer = format!(
+            "{:12} {:26} {:10} {:10} {:12} {}\n",
+            "ID", "Status", "Runtime", "Memory", "Lang", "Submitted"
+        );
+        for submission in self.submissions {
+            let status = format!("{:24}", submission.status_display);
+            let status = if submission.is_accepted() {
+                paint(Green, &format!("{} {}", Icon::Yes, status))
+            } else {
+                paint(Red, &format!("{} {}", Icon::_No, status))
+            };
+            buffer.push_str(&format!(
+                "{:<12} {} {:10} {:10} {:12} {}\n",
+                submission.id,
+                status,
+                submission.runtime,
+                submission.memory.as_deref().unwrap_or("N/A"),
+                submission.lang,
+                submission.time
+            ));
+        }
+
+        buffer
+    }
+
+    fn json(&self) -> Value {
+        serde_json::to_value(self.submissions).unwrap_or_default()
+    }
+}
+
+impl<'a> SubmissionListResult<'a> {
+    pub fn new(submissions: &'a [Submission]) -> Self {
+        Self { submissions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Printer, SubmissionListResult};
+    use crate::model::SubmissionList;
+    use serde_json::from_value;
+
+    #[test]
+    fn print_submission_list() {
+        let json_value = serde_json::json!({
+            "submissions_dump": [
+                {
+                    "id": 1044907055,
+                    "lang": "rust",
+                    "time": "1 year, 1 month",
+                    "timestamp": 1694280353,
+                    "status_display": "Accepted",
+                    "runtime": "0 ms",
+                    "url": "/submissions/detail/1044907055/",
+                    "is_pending": "Not Pending",
+                    "title": "Regular Expression Matching",
+                    "memory": "1.9 MB",
+                    "code": "impl Solution {}",
+                    "compare_result": "111",
+                    "title_slug": "regular-expression-matching"
+                },
+                {
+                    "id": 1044868319,
+                    "lang": "python3",
+                    "time": "1 year, 1 month",
+                    "timestamp": 1694277424,
+                    "status_display": "Wrong Answer",
+                    "runtime": "N/A",
+                    "memory": "N/A",
+                    "title_slug": "regular-expression-matching"
+                }
+            ],
+            "has_next": false,
+            "last_key": ""
+        });
+        let list = from_value::<SubmissionList>(json_value).unwrap();
+
+        let result = SubmissionListResult::new(&list.submissions_dump);
+        let buffer = String::from_utf8(strip_ansi_escapes::strip(result.buffer())).unwrap();
+
+        assert!(!result.is_error());
+        assert!(buffer.contains("1044907055   ✔ Accepted"));
+        assert!(buffer.contains("1044868319   ✘ Wrong Answer"));
+        assert!(buffer.contains("python3"));
+    }
+}
@@ -1,7 +1,9 @@
-use colci::Color;
+use ansi_term::Colour::{Green, Red};
+use serde_json::Value;
 
 use crate::model::ExecutionErrorResponse;
-use crate::printer::{decorator::bold_text, Printer, NEW_LINE};
+use crate::printer::{Printer, NEW_LINE};
+use crate::style::paint;
 use crate::{icon::Icon, model::SubmissionResponse, Either};
 
 #[derive(Debug)]
@@ -21,6 +23,10 @@ impl Printer for SubmitExecutionResult {
             self.success_buffer()
         }
     }
+
+    fn json(&self) -> Value {
+        self.submission_json(&self.submission_response)
+    }
 }
 
 impl SubmitExecutionResult {
@@ -61,44 +67,41 @@ impl SubmitExecutionResult {
 
     fn wrong_answer_buffer(&self) -> String {
         let mut buffer = String::new();
-        buffer.push_str(&bold_text(
-            &Color::Red(&format!(
+        buffer.push_str(&paint(
+            Red.bold(),
+            &format!(
                 "\n{} Wrong Answer: ({})\n\n",
-                Icon::_No.to_string(),
+                Icon::_No,
                 self.total_cases_ratio_buffer(&self.submission_response)
-            ))
-            .make(),
+            ),
         ));
         buffer.push_str(&self.last_test_case_buffer());
-        buffer.push_str(&Color::Red(&self.get_metas()).make());
+        buffer.push_str(&paint(Red, &self.get_metas()));
 
         buffer
     }
 
     fn last_test_case_buffer(&self) -> String {
         let mut buffer = String::new();
-        match (
+        if let (
+            Some(Either::String(input)),
+            Some(Either::String(ans)),
+            Some(Either::String(exp_ans)),
+        ) = (
             &self.submission_response.input,
             &self.submission_response.code_output,
             &self.submission_response.expected_output,
         ) {
-            (
-                Some(Either::String(input)),
-                Some(Either::String(ans)),
-                Some(Either::String(exp_ans)),
-            ) => {
-                let mut test_case = String::new();
-                test_case.push_str(&Color::Red("Last test case:\n").make());
-                test_case.push_str(&format!(
-                    "\tInput: \n\t\t{}\n",
-                    input.replace('\n', "\n\t\t")
-                ));
-                test_case.push_str(&format!("\n\tOutput: {}\n", ans));
-                test_case.push_str(&format!("\tExpected: {}\n\n", exp_ans));
+            let mut test_case = String::new();
+            test_case.push_str(&paint(Red, "Last test case:\n"));
+            test_case.push_str(&format!(
+                "\tInput: \n\t\t{}\n",
+                input.replace('\n', "\n\t\t")
+            ));
+            test_case.push_str(&format!("\n\tOutput: {}\n", ans));
+            test_case.push_str(&format!("\tExpected: {}\n\n", exp_ans));
 
-                buffer.push_str(test_case.as_str());
-            }
-            _ => {}
+            buffer.push_str(test_case.as_str());
         }
 
         buffer
@@ -106,16 +109,16 @@ impl SubmitExecutionResult {
 
     fn success_buffer(&self) -> String {
         let mut buffer = String::new();
-        buffer.push_str(&bold_text(
-            &Color::Green(&format!(
+        buffer.push_str(&paint(
+            Green.bold(),
+            &format!(
                 "{} Accepted: ({})\n\n",
-                Icon::Yes.to_string(),
+                Icon::Yes,
                 self.total_cases_ratio_buffer(&self.submission_response)
-            ))
-            .make(),
+            ),
         ));
         buffer.push_str(&self.last_test_case_buffer());
-        buffer.push_str(&Color::Green(&self.get_metas()).make());
+        buffer.push_str(&paint(Green, &self.get_metas()));
 
         buffer
     }
@@ -134,7 +137,7 @@ impl SubmitExecutionResult {
             .runtime_percentile
             .unwrap_or(0.0)
             .to_string();
-        let metas = vec![
+        let metas = [
             "Memory: ".to_string() + self.submission_response.status_memory.as_str(),
             "Memory %ile: ".to_string() + memory_percentile.as_str(),
             "Runtime: ".to_string() + self.submission_response.status_runtime.as_str(),
@@ -186,12 +189,11 @@ mod tests {
                     )
         .unwrap();
 
-        let response = from_value::<SubmissionResponse>(json_value).unwrap().into();
+        let response = from_value::<SubmissionResponse>(json_value).unwrap();
 
         let result = SubmitExecutionResult::new(response);
         result.print();
         // TODO implement snapshot testing
-        assert!(1 == 1);
     }
 
     #[test]
@@ -226,12 +228,11 @@ mod tests {
         )
         .unwrap();
 
-        let response = from_value::<SubmissionResponse>(json_value).unwrap().into();
+        let response = from_value::<SubmissionResponse>(json_value).unwrap();
 
         let result = SubmitExecutionResult::new(response);
         result.print();
         // TODO implement snapshot testing
-        assert!(1 == 1);
     }
 
     #[test]
@@ -269,11 +270,10 @@ r#"{
         )
         .unwrap();
 
-        let response = from_value::<SubmissionResponse>(json_value).unwrap().into();
+        let response = from_value::<SubmissionResponse>(json_value).unwrap();
 
         let result = SubmitExecutionResult::new(response);
         result.print();
         // TODO implement snapshot testing
-        assert!(1 == 1);
     }
 }
@@ -0,0 +1,96 @@
+use ansi_term::Colour::{Green, Red};
+use serde_json::{json, Value};
+
+use crate::icon::Icon;
+use crate::model::UserStatus;
+use crate::printer::{Printer, NEW_LINE};
+use crate::service::{utc_date, Session};
+use crate::style::paint;
+
+/// Print the status of the cached session, from `leetup user --status`.
+#[derive(Debug)]
+pub struct UserStatusResult {
+    status: UserStatus,
+    has_session: bool,
+    expires_at: Option<u64>,
+}
+
+impl Printer for UserStatusResult {
+    fn is_error(&self) -> bool {
+        !self.status.is_signed_in
+    }
+
+    fn buffer(&self) -> String {
+        if self.is_error() {
+            let msg = if self.has_session {
+                "Session is expired or invalid, login again using `leetup user -c` or `leetup user --import-cookies`"
+            } else {
+                "Not logged in, login using `leetup user -c` or `leetup user --import-cookies`"
+            };
+            return paint(Red, &format!("{} {}", Icon::_No, msg)) + NEW_LINE;
+        }
+
+        let mut buffer = paint(
+            Green,
+            &format!("{} Logged in as {}", Icon::Yes, self.status.username),
+        );
+        buffer.push_str(NEW_LINE);
+        buffer.push_str(&format!(
+            "Premium: {}\n",
+            if self.status.is_premium { "Yes" } else { "No" }
+        ));
+        buffer.push_str(&format!(
+            "Session expires: {}\n",
+            self.expires_at.map_or("Unknown".into(), utc_date)
+        ));
+
+        buffer
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "signed_in": self.status.is_signed_in,
+            "username": self.status.username,
+            "premium": self.status.is_premium,
+            "expires_at": self.expires_at,
+        })
+    }
+}
+
+impl UserStatusResult {
+    pub fn new(status: UserStatus, session: Option<&Session>) -> Self {
+        Self {
+            status,
+            has_session: session.is_some(),
+            expires_at: session.and_then(Session::expires_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Printer, UserStatusResult};
+    use crate::model::UserStatus;
+    use crate::service::Session;
+
+    #[test]
+    fn print_user_status() {
+        let status: UserStatus = serde_json::from_value(serde_json::json!({
+            "isSignedIn": true,
+            "username": "leetup",
+            "isPremium": false
+        }))
+        .unwrap();
+        let session = Session::new("session-id".into(), "csrf-token".into());
+        let result = UserStatusResult::new(status, Some(&session));
+        let buffer = String::from_utf8(strip_ansi_escapes::strip(result.buffer())).unwrap();
+        assert!(buffer.contains("Logged in as leetup"));
+        assert!(buffer.contains("Premium: No"));
+        assert!(buffer.contains("Session expires: Unknown"));
+
+        let result = UserStatusResult::new(UserStatus::default(), Some(&session));
+        assert!(result.is_error());
+        assert!(result.buffer().contains("Session is expired or invalid"));
+        assert_eq!(result.json()["signed_in"], false);
+    }
+}
@@ -1,31 +1,192 @@
+use std::fs;
 use std::io::{BufWriter, Write};
+use std::path::Path;
 
-use colci::Color;
+use ansi_term::Colour::Yellow;
+use anyhow::anyhow;
+use rusqlite::{Connection, OpenFlags};
 
 use crate::{
-    service::{ServiceProvider, Session},
-    Result,
+    printer::OutputFormat,
+    service::{self, ServiceProvider, Session},
+    style::paint,
+    LeetUpError, Result,
 };
 
-pub async fn cookie_login<'a, P: ServiceProvider<'a>>(_provider: &P) -> Result<Session> {
-    let mut out = BufWriter::new(std::io::stdout());
+const SESSION_COOKIE: &str = "LEETCODE_SESSION";
+const CSRF_COOKIE: &str = "csrftoken";
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+pub async fn cookie_login<'a, P: ServiceProvider<'a>>(provider: &P) -> Result<Session> {
+    // Keep stdout for the result alone with `--output json`
+    let mut out: Box<dyn Write> = if provider.config()?.output == OutputFormat::Json {
+        Box::new(std::io::stderr())
+    } else {
+        Box::new(BufWriter::new(std::io::stdout()))
+    };
     let stdin = std::io::stdin();
 
     let mut csrf = String::new();
     let mut lc_session = String::new();
 
-    write!(out, "{}", Color::Yellow("csrftoken: ").make())?;
+    write!(out, "{}", paint(Yellow, "csrftoken: "))?;
     out.flush()?;
     stdin.read_line(&mut csrf)?;
 
-    write!(out, "{}", Color::Yellow("LEETCODE_SESSION: ").make())?;
+    write!(out, "{}", paint(Yellow, "LEETCODE_SESSION: "))?;
     out.flush()?;
     stdin.read_line(&mut lc_session)?;
 
     csrf = csrf.trim().to_string();
     lc_session = lc_session.trim().to_string();
 
-    println!("{}", Color::Green("User logged in!").make());
-
     Ok(Session::new(lc_session.to_string(), csrf.to_string()))
 }
+
+/// A cookie exported from a browser.
+#[derive(Debug)]
+struct BrowserCookie {
+    domain: String,
+    name: String,
+    value: String,
+    /// Expiry as a unix timestamp, 0 for session cookies.
+    expires: u64,
+}
+
+impl BrowserCookie {
+    /// Whether the cookie is sent to `host`, e.g. `.leetcode.com` to `leetcode.com`.
+    fn matches_host(&self, host: &str) -> bool {
+        let domain = self.domain.trim_start_matches('.');
+        host == domain || host.ends_with(&format!(".{}", domain))
+    }
+
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires != 0 && self.expires < now
+    }
+}
+
+/// Read the session of `host` from cookies exported by a browser, either a Netscape
+/// `cookies.txt` or a copy of Firefox's `cookies.sqlite`.
+pub fn import_cookies(path: &Path, host: &str) -> Result<Session> {
+    let content = fs::read(path)?;
+    let cookies = if content.starts_with(SQLITE_HEADER) {
+        firefox_cookies(path)?
+    } else {
+        netscape_cookies(&String::from_utf8_lossy(&content))
+    };
+
+    let now = service::unix_timestamp();
+    let mut session = Session::default();
+    for cookie in cookies {
+        if !cookie.matches_host(host) || cookie.is_expired(now) {
+            continue;
+        }
+        match cookie.name.as_str() {
+            SESSION_COOKIE => session.id = cookie.value,
+            CSRF_COOKIE => session.csrf = cookie.value,
+            _ => (),
+        }
+    }
+
+    if session.id.is_empty() || session.csrf.is_empty() {
+        return Err(LeetUpError::Any(anyhow!(
+            "No {} and {} cookies of {} found in {}",
+            SESSION_COOKIE,
+            CSRF_COOKIE,
+            host,
+            path.display()
+        )));
+    }
+
+    Ok(session)
+}
+
+/// Parse cookies in the Netscape format, one tab separated cookie per line:
+/// `domain include_subdomains path secure expires name value`.
+fn netscape_cookies(content: &str) -> Vec<BrowserCookie> {
+    content
+        .lines()
+        .filter_map(|line| {
+            // HttpOnly cookies are exported as comments by some browsers
+            let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+            if line.starts_with('#') {
+                return None;
+            }
+            let fields: Vec<&str> = line.trim_end_matches('\r').split('\t').collect();
+            match fields.as_slice() {
+                [domain, _, _, _, expires, name, value] => Some(BrowserCookie {
+                    domain: domain.to_string(),
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    expires: expires.parse().unwrap_or_default(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Read LeetCode cookies from Firefox's `cookies.sqlite`.
+///
+/// The database is locked while Firefox runs, a copy of it should be used instead.
+fn firefox_cookies(path: &Path) -> Result<Vec<BrowserCookie>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt =
+        conn.prepare("SELECT host, name, value, expiry FROM moz_cookies WHERE name IN (?1, ?2)")?;
+    let cookies = stmt
+        .query_map([SESSION_COOKIE, CSRF_COOKIE], |row| {
+            let expiry: i64 = row.get(3)?;
+            Ok(BrowserCookie {
+                domain: row.get(0)?,
+                name: row.get(1)?,
+                value: row.get(2)?,
+                // Recent versions of Firefox store the expiry in milliseconds
+                expires: if expiry > 100_000_000_000 {
+                    expiry as u64 / 1000
+                } else {
+                    expiry.max(0) as u64
+                },
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(cookies)
+}
+
+#[test]
+fn test_netscape_cookies() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cookies.txt");
+    let content = "# Netscape HTTP Cookie File
+.leetcode.com\tTRUE\t/\tTRUE\t0\tcsrftoken\tcsrf-token
+#HttpOnly_.leetcode.com\tTRUE\t/\tTRUE\t4102444800\tLEETCODE_SESSION\tsession-id
+.leetcode.com\tTRUE\t/\tTRUE\t946684800\tLEETCODE_SESSION\texpired
+.leetcode.cn\tTRUE\t/\tTRUE\t0\tLEETCODE_SESSION\tcn-session
+";
+    fs::write(&path, content).unwrap();
+
+    let session = import_cookies(&path, "leetcode.com").unwrap();
+    assert_eq!(session.id, "session-id");
+    assert_eq!(session.csrf, "csrf-token");
+    assert!(import_cookies(&path, "leetcode.cn").is_err());
+}
+
+#[test]
+fn test_firefox_cookies() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cookies.sqlite");
+    let conn = Connection::open(&path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, name TEXT, value TEXT, host TEXT, expiry INTEGER);
+        INSERT INTO moz_cookies (name, value, host, expiry) VALUES
+            ('csrftoken', 'csrf-token', '.leetcode.cn', 4102444800),
+            ('LEETCODE_SESSION', 'session-id', 'leetcode.cn', 4102444800000),
+            ('LEETCODE_SESSION', 'other', '.github.com', 0);",
+    )
+    .unwrap();
+    drop(conn);
+
+    let session = import_cookies(&path, "leetcode.cn").unwrap();
+    assert_eq!(session.id, "session-id");
+    assert_eq!(session.csrf, "csrf-token");
+}
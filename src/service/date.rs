@@ -0,0 +1,41 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Seconds elapsed since UNIX epoch.
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Today's date in UTC formatted as `YYYY-MM-DD`.
+pub fn utc_date_today() -> String {
+    utc_date(unix_timestamp())
+}
+
+/// Format a UNIX timestamp as an UTC date `YYYY-MM-DD`.
+pub fn utc_date(timestamp: u64) -> String {
+    // Convert days since epoch to a civil date,
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (timestamp / SECS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[test]
+fn test_utc_date() {
+    assert_eq!(utc_date(0), "1970-01-01");
+    assert_eq!(utc_date(951_782_400), "2000-02-29");
+    assert_eq!(utc_date(1_694_280_355), "2023-09-09");
+    assert_eq!(utc_date(1_798_761_599), "2026-12-31");
+}
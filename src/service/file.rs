@@ -7,7 +7,7 @@ use std::str::FromStr;
 use log::*;
 
 use crate::model::Problem;
-use crate::{template::Pattern, LeetUpError, Result};
+use crate::{template::Pattern, LeetUpError, Result, Urls};
 
 impl FromStr for Problem {
     type Err = LeetUpError;
@@ -24,18 +24,17 @@ impl FromStr for Problem {
         let id: usize = map.get("id").unwrap().parse().unwrap();
         let slug = map.get("slug").unwrap().to_string();
         let lang = map.get("lang").unwrap().to_string();
-        let link = format!("https://leetcode.com/problems/{}/submissions/", slug);
         Ok(Self {
             id,
             slug,
             lang,
-            link,
+            link: String::new(),
             typed_code: None,
         })
     }
 }
 
-pub fn extract_problem<P: AsRef<Path>>(filename: P) -> Result<Problem> {
+pub fn extract_problem<P: AsRef<Path>>(filename: P, urls: &Urls) -> Result<Problem> {
     debug!("Filename: {:#?}", filename.as_ref());
     let mut typed_code = String::new();
     let mut file = File::open(filename)?;
@@ -49,6 +48,7 @@ pub fn extract_problem<P: AsRef<Path>>(filename: P) -> Result<Problem> {
     let end_index = line.find('\n').expect("LeetupInfo needs a new line");
     let line = &line[..end_index].trim();
     let mut problem = Problem::from_str(line)?;
+    problem.link = format!("{}{}/submissions/", urls.problems, problem.slug);
     problem.typed_code = Some(typed_code);
     debug!("{:#?}", problem);
 
@@ -0,0 +1,374 @@
+/// Data structures used by LeetCode problems, only given as comments in the default code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HelperTypes {
+    pub list: bool,
+    pub tree: bool,
+}
+
+impl HelperTypes {
+    pub fn all() -> Self {
+        HelperTypes {
+            list: true,
+            tree: true,
+        }
+    }
+
+    /// Types referenced by the default code of a problem.
+    pub fn detect(code: &str) -> Self {
+        HelperTypes {
+            list: code.contains("ListNode"),
+            tree: code.contains("TreeNode"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.list && !self.tree
+    }
+}
+
+/// Definitions of `types` in `lang` along with helpers to build them from LeetCode's notation,
+/// e.g. `[1,null,2]` to a tree, `None` if the language is not supported.
+pub fn helper_code(lang: &str, types: HelperTypes) -> Option<String> {
+    if types.is_empty() {
+        return None;
+    }
+
+    let (prelude, list, tree) = match lang {
+        "rust" => ("", RUST_LIST, RUST_TREE),
+        "python3" => (PYTHON_PRELUDE, PYTHON_LIST, PYTHON_TREE),
+        "cpp" => (CPP_PRELUDE, CPP_LIST, CPP_TREE),
+        "java" => (JAVA_PRELUDE, JAVA_LIST, JAVA_TREE),
+        _ => return None,
+    };
+
+    let mut code = vec![];
+    if !prelude.is_empty() {
+        code.push(prelude);
+    }
+    if types.list {
+        code.push(list);
+    }
+    if types.tree {
+        code.push(tree);
+    }
+
+    Some(code.join("\n").trim_end().to_owned())
+}
+
+const RUST_LIST: &str = r#"#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ListNode {
+    pub val: i32,
+    pub next: Option<Box<ListNode>>,
+}
+
+#[allow(dead_code)]
+impl ListNode {
+    #[inline]
+    fn new(val: i32) -> Self {
+        ListNode { next: None, val }
+    }
+}
+
+/// Build a linked list from its values, e.g. `[1,2,3]`.
+#[allow(dead_code)]
+pub fn to_list(values: Vec<i32>) -> Option<Box<ListNode>> {
+    let mut head = None;
+    for val in values.into_iter().rev() {
+        head = Some(Box::new(ListNode { val, next: head }));
+    }
+    head
+}
+
+#[allow(dead_code)]
+pub fn list_values(head: &Option<Box<ListNode>>) -> Vec<i32> {
+    let mut values = vec![];
+    let mut node = head;
+    while let Some(n) = node {
+        values.push(n.val);
+        node = &n.next;
+    }
+    values
+}
+"#;
+
+const RUST_TREE: &str = r#"#[derive(Debug, PartialEq, Eq)]
+pub struct TreeNode {
+    pub val: i32,
+    pub left: Option<std::rc::Rc<std::cell::RefCell<TreeNode>>>,
+    pub right: Option<std::rc::Rc<std::cell::RefCell<TreeNode>>>,
+}
+
+impl TreeNode {
+    #[inline]
+    pub fn new(val: i32) -> Self {
+        TreeNode {
+            val,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+/// Build a binary tree from its level order values, e.g. `[1,null,2]`.
+#[allow(dead_code)]
+pub fn to_tree(values: Vec<Option<i32>>) -> Option<std::rc::Rc<std::cell::RefCell<TreeNode>>> {
+    use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+    let new_node = |val| Rc::new(RefCell::new(TreeNode::new(val)));
+    let mut values = values.into_iter();
+    let root = new_node(values.next()??);
+    let mut queue = VecDeque::new();
+    queue.push_back(root.clone());
+    while let Some(node) = queue.pop_front() {
+        for is_left in [true, false].iter() {
+            match values.next() {
+                Some(Some(val)) => {
+                    let child = new_node(val);
+                    queue.push_back(child.clone());
+                    if *is_left {
+                        node.borrow_mut().left = Some(child);
+                    } else {
+                        node.borrow_mut().right = Some(child);
+                    }
+                }
+                Some(None) => {}
+                None => return Some(root),
+            }
+        }
+    }
+    Some(root)
+}
+
+#[allow(dead_code)]
+pub fn tree_values(root: &Option<std::rc::Rc<std::cell::RefCell<TreeNode>>>) -> Vec<Option<i32>> {
+    let mut values = vec![];
+    let mut queue = std::collections::VecDeque::new();
+    queue.push_back(root.clone());
+    while let Some(node) = queue.pop_front() {
+        match node {
+            Some(node) => {
+                let node = node.borrow();
+                values.push(Some(node.val));
+                queue.push_back(node.left.clone());
+                queue.push_back(node.right.clone());
+            }
+            None => values.push(None),
+        }
+    }
+    while let Some(None) = values.last() {
+        values.pop();
+    }
+    values
+}
+"#;
+
+const PYTHON_PRELUDE: &str = "from typing import *\n";
+
+const PYTHON_LIST: &str = r#"class ListNode:
+    def __init__(self, val=0, next=None):
+        self.val = val
+        self.next = next
+
+
+def to_list(values):
+    head = None
+    for val in reversed(values):
+        head = ListNode(val, head)
+    return head
+
+
+def list_values(head):
+    values = []
+    while head:
+        values.append(head.val)
+        head = head.next
+    return values
+"#;
+
+const PYTHON_TREE: &str = r#"class TreeNode:
+    def __init__(self, val=0, left=None, right=None):
+        self.val = val
+        self.left = left
+        self.right = right
+
+
+def to_tree(values):
+    if not values or values[0] is None:
+        return None
+    root = TreeNode(values[0])
+    queue, i = [root], 1
+    for node in queue:
+        for side in ("left", "right"):
+            if i >= len(values):
+                return root
+            if values[i] is not None:
+                child = TreeNode(values[i])
+                setattr(node, side, child)
+                queue.append(child)
+            i += 1
+    return root
+
+
+def tree_values(root):
+    values, queue = [], [root]
+    for node in queue:
+        values.append(node.val if node else None)
+        if node:
+            queue += [node.left, node.right]
+    while values and values[-1] is None:
+        values.pop()
+    return values
+"#;
+
+const CPP_PRELUDE: &str = "#include <bits/stdc++.h>\nusing namespace std;\n";
+
+const CPP_LIST: &str = r#"struct ListNode {
+    int val;
+    ListNode *next;
+    ListNode() : val(0), next(nullptr) {}
+    ListNode(int x) : val(x), next(nullptr) {}
+    ListNode(int x, ListNode *next) : val(x), next(next) {}
+};
+
+ListNode* to_list(const vector<int>& values) {
+    ListNode* head = nullptr;
+    for (auto it = values.rbegin(); it != values.rend(); it++) {
+        head = new ListNode(*it, head);
+    }
+    return head;
+}
+
+vector<int> list_values(ListNode* head) {
+    vector<int> values;
+    for (; head; head = head->next) {
+        values.push_back(head->val);
+    }
+    return values;
+}
+"#;
+
+const CPP_TREE: &str = r#"struct TreeNode {
+    int val;
+    TreeNode *left;
+    TreeNode *right;
+    TreeNode() : val(0), left(nullptr), right(nullptr) {}
+    TreeNode(int x) : val(x), left(nullptr), right(nullptr) {}
+    TreeNode(int x, TreeNode *left, TreeNode *right) : val(x), left(left), right(right) {}
+};
+
+TreeNode* to_tree(const vector<optional<int>>& values) {
+    if (values.empty() || !values[0]) return nullptr;
+    TreeNode* root = new TreeNode(*values[0]);
+    queue<TreeNode*> nodes;
+    nodes.push(root);
+    size_t i = 1;
+    while (!nodes.empty() && i < values.size()) {
+        TreeNode* node = nodes.front();
+        nodes.pop();
+        if (i < values.size() && values[i]) {
+            node->left = new TreeNode(*values[i]);
+            nodes.push(node->left);
+        }
+        i++;
+        if (i < values.size() && values[i]) {
+            node->right = new TreeNode(*values[i]);
+            nodes.push(node->right);
+        }
+        i++;
+    }
+    return root;
+}
+
+vector<optional<int>> tree_values(TreeNode* root) {
+    vector<optional<int>> values;
+    queue<TreeNode*> nodes;
+    nodes.push(root);
+    while (!nodes.empty()) {
+        TreeNode* node = nodes.front();
+        nodes.pop();
+        if (node) {
+            values.push_back(node->val);
+            nodes.push(node->left);
+            nodes.push(node->right);
+        } else {
+            values.push_back(nullopt);
+        }
+    }
+    while (!values.empty() && !values.back()) values.pop_back();
+    return values;
+}
+"#;
+
+const JAVA_PRELUDE: &str = "import java.util.*;\n";
+
+const JAVA_LIST: &str = r#"class ListNode {
+    int val;
+    ListNode next;
+    ListNode() {}
+    ListNode(int val) { this.val = val; }
+    ListNode(int val, ListNode next) { this.val = val; this.next = next; }
+
+    static ListNode of(int... values) {
+        ListNode head = null;
+        for (int i = values.length - 1; i >= 0; i--) {
+            head = new ListNode(values[i], head);
+        }
+        return head;
+    }
+}
+"#;
+
+const JAVA_TREE: &str = r#"class TreeNode {
+    int val;
+    TreeNode left;
+    TreeNode right;
+    TreeNode() {}
+    TreeNode(int val) { this.val = val; }
+    TreeNode(int val, TreeNode left, TreeNode right) {
+        this.val = val;
+        this.left = left;
+        this.right = right;
+    }
+
+    static TreeNode of(Integer... values) {
+        if (values.length == 0 || values[0] == null) return null;
+        TreeNode root = new TreeNode(values[0]);
+        Queue<TreeNode> nodes = new LinkedList<>();
+        nodes.add(root);
+        for (int i = 1; i < values.length; i += 2) {
+            TreeNode node = nodes.poll();
+            if (values[i] != null) {
+                node.left = new TreeNode(values[i]);
+                nodes.add(node.left);
+            }
+            if (i + 1 < values.length && values[i + 1] != null) {
+                node.right = new TreeNode(values[i + 1]);
+                nodes.add(node.right);
+            }
+        }
+        return root;
+    }
+}
+"#;
+
+#[test]
+fn test_helper_code() {
+    let code = "/**\n * Definition for singly-linked list.\n * public class ListNode {";
+    let types = HelperTypes::detect(code);
+    assert_eq!(
+        types,
+        HelperTypes {
+            list: true,
+            tree: false
+        }
+    );
+
+    let rust = helper_code("rust", types).unwrap();
+    assert!(rust.contains("pub struct ListNode"));
+    assert!(!rust.contains("TreeNode"));
+    assert!(helper_code("cpp", HelperTypes::all())
+        .unwrap()
+        .starts_with("#include <bits/stdc++.h>"));
+    assert!(helper_code("golang", HelperTypes::all()).is_none());
+    assert!(helper_code("rust", HelperTypes::default()).is_none());
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::{header, Response};
+use serde_json::Value;
+use spinners::{Spinner, Spinners};
+
+/// State of a run or submission reported by the judge's `check` endpoint.
+#[derive(Debug, PartialEq, Eq)]
+pub enum JudgeState {
+    Pending,
+    Judging { done: u64, total: u64 },
+    Started,
+    Success,
+    Failure(String),
+}
+
+impl From<&Value> for JudgeState {
+    fn from(response: &Value) -> Self {
+        match response["state"].as_str() {
+            Some("PENDING") => JudgeState::Pending,
+            Some("STARTED") => {
+                match (
+                    response["total_correct"].as_u64(),
+                    response["total_testcases"].as_u64(),
+                ) {
+                    (Some(done), Some(total)) if total > 0 => JudgeState::Judging { done, total },
+                    _ => JudgeState::Started,
+                }
+            }
+            Some("SUCCESS") => JudgeState::Success,
+            Some(state) => JudgeState::Failure(state.to_owned()),
+            None => JudgeState::Failure("UNKNOWN".to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for JudgeState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JudgeState::Pending => write!(f, "Pending"),
+            JudgeState::Judging { done, total } => write!(f, "Judging {}/{}", done, total),
+            JudgeState::Started => write!(f, "Judging"),
+            JudgeState::Success => write!(f, "Finished"),
+            JudgeState::Failure(state) => write!(f, "Failed with state {}", state),
+        }
+    }
+}
+
+/// Exponential backoff between polls, capped at `max`.
+#[derive(Debug)]
+pub struct Backoff {
+    delay: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Backoff {
+            delay: initial,
+            max,
+        }
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let delay = self.delay;
+        self.delay = (delay * 2).min(self.max);
+        Some(delay)
+    }
+}
+
+/// Delay requested by a rate limited response through `Retry-After` seconds.
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Spinner showing the progress of the judge, hidden when results are not printed as text.
+pub struct Progress(Option<Spinner>);
+
+impl Progress {
+    pub fn new(visible: bool) -> Self {
+        Progress::with_message(visible, "Waiting for judge result!")
+    }
+
+    pub fn with_message(visible: bool, message: &str) -> Self {
+        Progress(visible.then(|| Spinner::new(Spinners::Dots9, message.into())))
+    }
+
+    pub fn message(&self, message: String) {
+        if let Some(ref spinner) = self.0 {
+            spinner.message(message);
+        }
+    }
+
+    pub fn stop(self) {
+        if let Some(spinner) = self.0 {
+            spinner.stop();
+        }
+    }
+}
+
+/// Bar of `width` characters filled in proportion to `done` out of `total`.
+pub fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = (done * width)
+        .checked_div(total)
+        .unwrap_or(width)
+        .min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+#[test]
+fn test_progress_bar() {
+    assert_eq!("[----------]", progress_bar(0, 4, 10));
+    assert_eq!("[#####-----]", progress_bar(2, 4, 10));
+    assert_eq!("[##########]", progress_bar(4, 4, 10));
+    assert_eq!("[##########]", progress_bar(0, 0, 10));
+}
+
+#[test]
+fn test_judge_state() {
+    use serde_json::json;
+
+    assert_eq!(
+        JudgeState::Pending,
+        JudgeState::from(&json!({"state": "PENDING"}))
+    );
+    assert_eq!(
+        "Judging 57/120",
+        JudgeState::from(&json!({"state": "STARTED", "total_correct": 57, "total_testcases": 120}))
+            .to_string()
+    );
+    assert_eq!(
+        JudgeState::Started,
+        JudgeState::from(&json!({"state": "STARTED"}))
+    );
+    assert_eq!(
+        JudgeState::Success,
+        JudgeState::from(&json!({"state": "SUCCESS", "status_code": 10}))
+    );
+    assert_eq!(
+        JudgeState::Failure("FAILURE".to_owned()),
+        JudgeState::from(&json!({"state": "FAILURE"}))
+    );
+}
+
+#[test]
+fn test_backoff() {
+    let delays: Vec<u64> = Backoff::new(Duration::from_millis(200), Duration::from_millis(1000))
+        .take(5)
+        .map(|d| d.as_millis() as u64)
+        .collect();
+    assert_eq!(vec![200, 400, 800, 1000, 1000], delays);
+}
@@ -1,41 +1,93 @@
 use std::cmp::Ord;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::env;
 use std::fs::{self, File};
 use std::io::{prelude::*, stdin};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use ansi_term::Colour::{Cyan, Green, Purple, Red, Yellow};
 use anyhow::anyhow;
 use async_trait::async_trait;
-use colci::Color;
+use futures::stream::StreamExt;
 use html2text::from_read;
 use leetup_cache::kvstore::KvStore;
 use log::{debug, info};
+use rand::Rng;
+use regex::Regex;
 use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::StatusCode;
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 
 use crate::model::{
-    CodeDefinition, Problem, ProblemInfo, ProblemInfoSeq, StatStatusPair, SubmissionResponse,
-    TopicTagQuestion,
+    CachedDaily, CachedQuestion, CodeDefinition, ListResponse, Problem, ProblemInfo,
+    ProblemInfoSeq, QuestionDetail, StatStatusPair, SubmissionList, SubmissionResponse,
+    TopicTagQuestion, UserStatus,
 };
-use crate::printer::SubmitExecutionResult;
+use crate::printer::{
+    decorator::normalize_html, human_size, CacheEntry, CacheStatsResult, LocalRunResult,
+    MessageResult, ProblemListResult, QuestionDetailResult, SubmissionListResult,
+    SubmitExecutionResult, UserStatusResult,
+};
+use crate::service::judge::{progress_bar, retry_after, Backoff, JudgeState, Progress};
+use crate::style::paint;
 use crate::template::parse_code;
 use crate::{
     client::RemoteClient,
     cmd::{self, List, OrderBy, Query, User},
-    printer::{Printer, TestExecutionResult},
-    service::{self, auth, CacheKey, Comment, CommentStyle, LangInfo, ServiceProvider, Session},
+    printer::{OutputFormat, Printer, TestExecutionResult},
+    service::{
+        self, auth, helper_code, CacheKey, CargoProject, Comment, CommentStyle, Harness,
+        HelperTypes, Lang, LangInfo, LazySession, ServiceProvider, Session, Signature,
+        TestScaffold,
+    },
     template::{InjectPosition, Pattern},
-    Config, Either, LeetUpError, Result,
+    Config, Either, InjectCode, LeetUpError, ProjectLayout, Result, Site,
 };
 
+const QUESTION_DETAIL_QUERY: &str = r#"
+    query getQuestionDetail($titleSlug: String!) {
+       question(titleSlug: $titleSlug) {
+         questionFrontendId
+         title
+         titleSlug
+         difficulty
+         content
+         stats
+         likes
+         dislikes
+         codeDefinition
+         sampleTestCase
+         exampleTestcases
+         enableRunCode
+         metaData
+         translatedTitle
+         translatedContent
+         hints
+         topicTags {
+           name
+           slug
+         }
+       }
+    }
+"#;
+
+/// Version of the cached question details, changes along with `QUESTION_DETAIL_QUERY`
+/// so that details missing newly queried fields are fetched again.
+fn question_detail_version() -> String {
+    let digest = Sha256::digest(QUESTION_DETAIL_QUERY.as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /// Leetcode holds all attributes required to implement ServiceProvider trait.
 pub struct Leetcode<'a> {
     /// Store user session
     ///
     /// If session is empty, user should be able to view problems.
-    session: Option<&'a Session>,
+    session: &'a LazySession,
 
     /// Get config from config.json
     config: &'a Config,
@@ -44,6 +96,7 @@ pub struct Leetcode<'a> {
     cache: KvStore,
 
     /// Service provider name
+    #[allow(dead_code)]
     name: &'a str,
 
     remote_client: RemoteClient<'a>,
@@ -51,8 +104,8 @@ pub struct Leetcode<'a> {
 
 #[async_trait]
 impl<'a> ServiceProvider<'a> for Leetcode<'a> {
-    fn session(&self) -> Option<&Session> {
-        self.session
+    fn session(&self) -> Result<Option<&Session>> {
+        self.session.get()
     }
 
     fn config(&self) -> Result<&Config> {
@@ -61,15 +114,23 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
 
     /// Fetch all problems
     ///
-    /// Use cache wherever necessary
+    /// Use cache wherever necessary, expired problems are still used offline.
     async fn fetch_all_problems(&mut self) -> Result<Value> {
+        let key = self.cache_key(CacheKey::Problems);
+        let cached = if self.config.offline {
+            self.cache.get_stale(key)?
+        } else {
+            self.cache.get(key)?
+        };
         let problems_res: Value;
-        if let Some(ref val) = self.cache.get(CacheKey::Problems.into())? {
+        if let Some(ref val) = cached {
             debug!("Fetching problems from cache...");
             problems_res = serde_json::from_str::<Value>(val)?;
+        } else if self.config.offline {
+            return Err(LeetUpError::NotCached("List of problems".into()));
         } else {
             let url = &self.config.urls.problems_all;
-            let session = self.session();
+            let session = self.session()?;
             problems_res = self
                 .remote_client
                 .get(url, None, session)
@@ -78,7 +139,11 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
                 .await
                 .map_err(LeetUpError::Reqwest)?;
             let res_serialized = serde_json::to_string(&problems_res)?;
-            self.cache.set(CacheKey::Problems.into(), res_serialized)?;
+            let key = self.cache_key(CacheKey::Problems);
+            match self.config.cache.problems_ttl() {
+                Some(ttl) => self.cache.set_with_ttl(key, res_serialized, ttl)?,
+                None => self.cache.set(key, res_serialized)?,
+            }
         }
 
         Ok(problems_res)
@@ -86,10 +151,7 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
 
     async fn list_problems(&mut self, list: List) -> Result<()> {
         if !self.is_user_logged_in() {
-            print!(
-                "{}",
-                Color::Red("You need to login to list problems").make()
-            );
+            print!("{}", paint(Red, "You need to login to list problems"));
             return Ok(());
         }
 
@@ -115,45 +177,51 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
 
         if let Some(ref order) = list.order {
             let orders = OrderBy::from_str(order);
-            probs.sort_by(|a, b| Leetcode::with_ordering(orders.as_slice(), a, b));
+            probs
+                .sort_by(|a, b| Leetcode::with_ordering(orders.as_slice(), a.as_ref(), b.as_ref()));
         } else {
             probs.sort_by(Ord::cmp);
         }
 
         if list.query.is_some() || list.keyword.is_some() {
-            let filter_predicate = |o: &Box<dyn ProblemInfo + Send>| {
-                let default_keyword = String::from("");
-                let keyword = list
-                    .keyword
-                    .as_ref()
-                    .unwrap_or(&default_keyword)
-                    .to_ascii_lowercase();
+            let keyword = list
+                .keyword
+                .as_deref()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let queries = list.query.as_deref().map(Query::from_str);
+
+            probs.retain(|o| {
                 let has_keyword = o.question_title().to_lowercase().contains(&keyword);
 
-                return list
-                    .query
+                queries
                     .as_ref()
-                    .map(|query| Query::from_str(query))
-                    .map(|queries| Leetcode::apply_queries(&queries, o))
+                    .map(|queries| Leetcode::apply_queries(queries, o.as_ref()))
                     .map(|result| has_keyword && result)
-                    .unwrap_or(has_keyword);
-            };
+                    .unwrap_or(has_keyword)
+            });
+        }
 
-            Leetcode::pretty_list(
-                &probs
-                    .into_iter()
-                    .filter(filter_predicate)
-                    .collect::<Vec<Box<dyn ProblemInfo + Send + 'static>>>(),
-            );
+        let list_response: Option<ListResponse> = if list.stat {
+            Some(serde_json::from_value(problems_res)?)
         } else {
-            Leetcode::pretty_list(probs.iter());
-        }
+            None
+        };
+        ProblemListResult::new(&probs, list_response.as_ref()).print_as(self.config.output);
 
         Ok(())
     }
 
     async fn pick_problem(&mut self, pick: cmd::Pick) -> Result<()> {
-        let probs = self.fetch_problems().await?;
+        let item = if pick.random {
+            self.random_problem(&pick).await?
+        } else {
+            let key = pick.id.as_ref().ok_or_else(|| {
+                LeetUpError::Any(anyhow!("Expected a problem ID, title slug or title"))
+            })?;
+            self.find_problem(key).await?
+        };
+        self.add_pick_history(&item.stat.question_title_slug, pick.days)?;
         let urls = &self.config.urls;
         let lang = pick
             .lang
@@ -161,58 +229,91 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
             .map(|l| l.info())
             .unwrap_or(self.config.lang.info());
 
-        let problem: Problem = probs
-            .iter()
-            .find(|item| {
-                item.stat.frontend_question_id == pick.id.expect("Expected frontend_question_id")
-            })
-            .map(|item| Problem {
-                id: item.stat.frontend_question_id,
-                link: format!("{}{}/", urls.problems, item.stat.question_title_slug),
-                slug: item.stat.question_title_slug.to_string(),
-                lang: lang.name.to_owned(),
-                typed_code: None,
-            })
-            .expect("Problem with given ID not found");
+        let problem = Problem {
+            id: item.stat.frontend_question_id,
+            link: format!("{}{}/", urls.problems, item.stat.question_title_slug),
+            slug: item.stat.question_title_slug.to_string(),
+            lang: lang.name.to_owned(),
+            typed_code: None,
+        };
 
         let problem_id = problem.id;
         let slug = problem.slug.to_owned();
-        let query = r#"
-            query getQuestionDetail($titleSlug: String!) {
-               question(titleSlug: $titleSlug) {
-                 content
-                 stats
-                 likes
-                 dislikes
-                 codeDefinition
-                 sampleTestCase
-                 enableRunCode
-                 metaData
-                 translatedContent
-               }
+        let response = self.problem_detail(&slug, pick.refresh).await?;
+
+        self.generate_problem_stub(&lang, &problem, problem_id, slug, &response)?;
+
+        Ok(())
+    }
+
+    async fn show_problem(&mut self, show: cmd::Show) -> Result<()> {
+        let problem = self.find_problem(&show.id).await?;
+        let slug = &problem.stat.question_title_slug;
+        let link = format!("{}{}/", self.config.urls.problems, slug);
+        let response = self.problem_detail(slug, show.refresh).await?;
+        let question: QuestionDetail =
+            serde_json::from_value(response["data"]["question"].clone())?;
+
+        QuestionDetailResult::new(link, question).print_as(self.config.output);
+
+        Ok(())
+    }
+
+    async fn daily_problem(&mut self, daily: cmd::Daily) -> Result<()> {
+        // Daily challenge changes at midnight UTC
+        let today = service::utc_date_today();
+        let key = self.cache_key(CacheKey::Daily);
+        let cached = match self.cache.get(key.clone())? {
+            Some(ref val) => serde_json::from_str::<CachedDaily>(val).ok(),
+            None => None,
+        };
+        let response: Value;
+        match cached {
+            Some(cached) if cached.date == today && !daily.refresh => {
+                debug!("Fetching daily challenge from cache...");
+                response = self.problem_detail(&cached.slug, false).await?;
             }
-        "#;
-        let body: Value = json!({
-            "query": query,
-            "variables": json!({
-                "titleSlug": slug.to_owned(),
-            }),
-            "operationName": "getQuestionDetail"
-        });
+            _ => {
+                let slug = self.fetch_daily_slug().await?;
+                response = self.problem_detail(&slug, daily.refresh).await?;
+                let cached = CachedDaily { date: today, slug };
+                self.cache.set(key, serde_json::to_string(&cached)?)?;
+            }
+        }
 
-        let response = self
-            .remote_client
-            .post(&urls.graphql, &body, || None)
-            .await?;
-        debug!("Response: {}", response);
+        let question: QuestionDetail =
+            serde_json::from_value(response["data"]["question"].clone())?;
+        let slug = question.title_slug.to_owned().ok_or(LeetUpError::OptNone)?;
+        let problem_id: usize = question
+            .question_frontend_id
+            .as_ref()
+            .and_then(|id| id.parse().ok())
+            .ok_or(LeetUpError::OptNone)?;
+        let link = format!("{}{}/", self.config.urls.problems, slug);
 
-        self.generate_problem_stub(&lang, &problem, problem_id, slug, &response)?;
+        if daily.generate {
+            let lang = daily
+                .lang
+                .as_ref()
+                .map(|l| l.info())
+                .unwrap_or(self.config.lang.info());
+            let problem = Problem {
+                id: problem_id,
+                link,
+                slug: slug.to_owned(),
+                lang: lang.name.to_owned(),
+                typed_code: None,
+            };
+            self.generate_problem_stub(&lang, &problem, problem_id, slug, &response)?;
+        } else {
+            QuestionDetailResult::new(link, question).print_as(self.config.output);
+        }
 
         Ok(())
     }
 
     async fn problem_test(&self, test: cmd::Test) -> Result<()> {
-        let problem = service::extract_problem(test.filename)?;
+        let problem = service::extract_problem(test.filename, &self.config.urls)?;
 
         let test_data = self.get_test_data(test.test_data);
         debug!("Test data: {:?}", test_data);
@@ -226,17 +327,14 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
         });
         let url = &self.config()?.urls.test;
         debug!("problem_test url: {}, {:?}", url, body);
+        let spinner = Progress::new(self.config.output.is_interactive());
         let response = self.run_code(url, &problem, body).await;
         debug!("problem_test response: {:?}", response);
 
         match response {
             Err(e) => {
-                println!("\n\n{}", Color::Red(e.to_string().as_str()).make());
-                println!(
-                    "\n{}",
-                    Color::Yellow("Note: If error status is 4XX, make sure you are logged in!")
-                        .make()
-                );
+                spinner.stop();
+                return Err(e);
             }
             Ok(json) => {
                 let url = self.config.urls.verify.replace(
@@ -245,10 +343,11 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
                         LeetUpError::Any(anyhow!("Unable to replace `interpret_id`"))
                     })?,
                 );
-                let result: SubmissionResponse =
-                    serde_json::from_value(self.verify_run_code(&url).await?)?;
+                let result = self.verify_run_code(&url, &spinner).await;
+                spinner.stop();
+                let result: SubmissionResponse = serde_json::from_value(result?)?;
                 let execution_result = TestExecutionResult::new(test_data.into(), result);
-                execution_result.print();
+                execution_result.print_as(self.config.output);
             }
         }
 
@@ -256,7 +355,7 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
     }
 
     async fn problem_submit(&self, submit: cmd::Submit) -> Result<()> {
-        let problem = service::extract_problem(submit.filename)?;
+        let problem = service::extract_problem(submit.filename, &self.config.urls)?;
         let body = json!({
             "lang":        problem.lang.to_owned(),
             "question_id": problem.id,
@@ -265,15 +364,119 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
             "judge_type": "large",
         });
         let url = &self.config()?.urls.submit;
-        let response = self.run_code(url, &problem, body).await?;
-        let url = self
-            .config
-            .urls
-            .verify
-            .replace("$id", &response["submission_id"].to_string());
-        let result: SubmissionResponse = serde_json::from_value(self.verify_run_code(&url).await?)?;
+        let spinner = Progress::new(self.config.output.is_interactive());
+        let result = match self.run_code(url, &problem, body).await {
+            Ok(response) => {
+                let url = self
+                    .config
+                    .urls
+                    .verify
+                    .replace("$id", &response["submission_id"].to_string());
+                self.verify_run_code(&url, &spinner).await
+            }
+            Err(e) => Err(e),
+        };
+        spinner.stop();
+        let result: SubmissionResponse = serde_json::from_value(result?)?;
         let execution_result = SubmitExecutionResult::new(result);
-        execution_result.print();
+        execution_result.print_as(self.config.output);
+        Ok(())
+    }
+
+    async fn problem_run(&mut self, run: cmd::Run) -> Result<()> {
+        let problem = service::extract_problem(run.filename, &self.config.urls)?;
+        let lang = Lang::from_str(&problem.lang)?;
+        let code = problem
+            .typed_code
+            .as_deref()
+            .and_then(parse_code)
+            .ok_or(LeetUpError::OptNone)?;
+
+        let response = self.problem_detail(&problem.slug, false).await?;
+        let question: QuestionDetail =
+            serde_json::from_value(response["data"]["question"].clone())?;
+        let meta = question
+            .meta_data()
+            .ok_or_else(|| LeetUpError::Any(anyhow!("Problem has no signature to run locally")))?;
+        let signature = Signature::try_from(&meta)?;
+        let test_data = run
+            .test_data
+            .or(question.example_testcases)
+            .or(question.sample_test_case)
+            .unwrap_or_default();
+        let cases = signature.test_cases(&test_data)?;
+
+        let harness = Harness::new(&lang, &code, &signature, &cases)?;
+        // A private directory per run, removed once done
+        let dir = tempfile::Builder::new().prefix("leetup-").tempdir()?;
+        debug!("Running {} locally in {:?}", problem.slug, dir.path());
+        let run = harness.run(dir.path(), &cases, self.config.run.timeout())?;
+        LocalRunResult::new(run).print_as(self.config.output);
+
+        Ok(())
+    }
+
+    async fn problem_submissions(&mut self, submissions: cmd::Submissions) -> Result<()> {
+        let (slug, problem_id) = if Path::new(&submissions.problem).is_file() {
+            let problem = service::extract_problem(&submissions.problem, &self.config.urls)?;
+            (problem.slug, problem.id)
+        } else {
+            let item = self.find_problem(&submissions.problem).await?;
+            (
+                item.stat.question_title_slug,
+                item.stat.frontend_question_id,
+            )
+        };
+
+        let url = format!(
+            "{}?offset=0&limit={}",
+            self.config.urls.submissions.replace("$slug", &slug),
+            submissions.limit
+        );
+        let response = self
+            .remote_client
+            .get(&url, None, self.session()?)
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+        let list: SubmissionList = serde_json::from_value(response)?;
+
+        let download = match submissions.download {
+            Some(download) => download,
+            None => {
+                SubmissionListResult::new(&list.submissions_dump).print_as(self.config.output);
+                return Ok(());
+            }
+        };
+
+        let submission = match download {
+            Some(id) => list.submissions_dump.iter().find(|s| s.id == id),
+            None => list.submissions_dump.iter().find(|s| s.is_accepted()),
+        }
+        .ok_or_else(|| {
+            LeetUpError::Any(anyhow!(
+                "Submission not found in the last {} submissions",
+                submissions.limit
+            ))
+        })?;
+
+        let typed_code = match submission.code {
+            Some(ref code) => code.to_owned(),
+            None => self.fetch_submission_code(submission.id).await?,
+        };
+        let lang = Lang::from_str(&submission.lang)?.info();
+        let problem = Problem {
+            id: problem_id,
+            link: format!("{}{}/", self.config.urls.problems, slug),
+            slug: slug.to_owned(),
+            lang: lang.name.to_owned(),
+            typed_code: Some(typed_code),
+        };
+        let response = self.problem_detail(&slug, false).await?;
+
+        self.generate_problem_stub(&lang, &problem, problem_id, slug, &response)?;
+
         Ok(())
     }
 
@@ -282,16 +485,217 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
         if user.cookie.is_some() {
             let session = auth::cookie_login(self).await?;
             self.cache_session(session)?;
+            MessageResult::new(paint(Green, "User logged in!"), json!({"logged_in": true}))
+                .print_as(self.config.output);
+        }
+
+        if let Some(ref path) = user.import_cookies {
+            let base = url::Url::parse(&self.config.urls.base)
+                .map_err(|e| LeetUpError::Any(anyhow!("Invalid base url: {}", e)))?;
+            let host = base.host_str().unwrap_or_default();
+            let session = auth::import_cookies(path, host)?;
+            let username = self.verify_session(&session).await?;
+            self.cache_session(session)?;
+            MessageResult::new(
+                paint(Green, &format!("User logged in as {}!", username)),
+                json!({"logged_in": true, "username": username}),
+            )
+            .print_as(self.config.output);
+        }
+
+        if user.status {
+            let status = self.user_status(self.session()?).await?;
+            UserStatusResult::new(status, self.session()?).print_as(self.config.output);
         }
 
         if user.logout.is_some() {
-            self.logout()?;
-            println!("User logged out!");
+            let logged_out = self.logout()?;
+            let text = if logged_out {
+                "User logged out!"
+            } else {
+                "User not logged in!"
+            };
+            MessageResult::new(text.to_owned(), json!({"logged_out": logged_out}))
+                .print_as(self.config.output);
+        }
+
+        Ok(())
+    }
+
+    async fn process_cache(&mut self, cache: cmd::Cache) -> Result<()> {
+        let profile = self.config.profile.as_deref();
+        match cache {
+            cmd::Cache::Stats => {
+                let now = service::unix_timestamp();
+                let entries = self
+                    .cache
+                    .entries()
+                    .filter_map(|entry| {
+                        CacheKey::unscoped(entry.key, profile).map(|key| CacheEntry {
+                            key: key.to_owned(),
+                            size: entry.size,
+                            expires_at: entry.expires_at,
+                            expired: entry.is_expired(now),
+                        })
+                    })
+                    .collect();
+                CacheStatsResult::new(entries, self.cache.log_size()?, self.cache.stale_size())
+                    .print_as(self.config.output);
+            }
+            cmd::Cache::Refresh => {
+                let _ = self.cache.remove(self.cache_key(CacheKey::Problems));
+                let problems = self.fetch_all_problems().await?;
+                let count = problems["stat_status_pairs"].as_array().map_or(0, Vec::len);
+                MessageResult::new(
+                    paint(Green, &format!("Refreshed {} problems", count)),
+                    json!({"refreshed": count}),
+                )
+                .print_as(self.config.output);
+            }
+            cmd::Cache::Clear { key } => {
+                let session: String = CacheKey::Session.into();
+                let keys: Vec<String> = self
+                    .cache
+                    .entries()
+                    .filter(|entry| {
+                        CacheKey::unscoped(entry.key, profile).is_some_and(|k| match key {
+                            Some(ref key) => k == key,
+                            None => k != session,
+                        })
+                    })
+                    .map(|entry| entry.key.to_owned())
+                    .collect();
+                if let (Some(key), true) = (&key, keys.is_empty()) {
+                    return Err(LeetUpError::Any(anyhow!(
+                        "Key `{}` not found in the cache",
+                        key
+                    )));
+                }
+                for key in &keys {
+                    self.cache.remove(key.to_owned())?;
+                }
+                self.cache.compact()?;
+                MessageResult::new(
+                    format!("Removed {} entries", keys.len()),
+                    json!({"removed": keys.len()}),
+                )
+                .print_as(self.config.output);
+            }
+            cmd::Cache::Compact => {
+                let size = self.cache.log_size()?;
+                let expired = self.cache.remove_expired()?;
+                self.cache.compact()?;
+                let compacted_size = self.cache.log_size()?;
+                MessageResult::new(
+                    format!(
+                        "Compacted cache from {} to {}",
+                        human_size(size),
+                        human_size(compacted_size)
+                    ),
+                    json!({"size": size, "compacted_size": compacted_size, "expired": expired}),
+                )
+                .print_as(self.config.output);
+            }
         }
 
         Ok(())
     }
 
+    /// Download details of all free problems, with at most `sync.jobs` requests in flight.
+    ///
+    /// Details are cached as soon as they are downloaded and problems cached already
+    /// are skipped, so an interrupted sync resumes where it stopped.
+    async fn sync_problems(&mut self, sync: cmd::Sync) -> Result<()> {
+        if self.config.offline {
+            return Err(LeetUpError::Offline(self.config.urls.graphql.to_owned()));
+        }
+        let probs = self.fetch_problems().await?;
+        let free = probs.iter().filter(|item| !item.paid_only).count();
+        let mut slugs = vec![];
+        for item in probs.into_iter().filter(|item| !item.paid_only) {
+            let slug = item.stat.question_title_slug;
+            let cached = self.cached_problem(&self.cache_key(CacheKey::Problem(&slug)))?;
+            if sync.refresh || !cached.is_some_and(|cached| self.is_problem_fresh(&cached)) {
+                slugs.push(slug);
+            }
+        }
+
+        let total = slugs.len();
+        let progress = Progress::with_message(
+            self.config.output.is_interactive(),
+            &format!("Syncing {} problems", total),
+        );
+        let (remote_client, config) = (&self.remote_client, self.config);
+        let mut details = remote_client.execute_all(slugs, sync.jobs, |slug| async move {
+            let response = Leetcode::fetch_problem_detail(remote_client, config, &slug).await;
+            (slug, response)
+        });
+
+        let mut failed = vec![];
+        let mut done = 0;
+        while let Some((slug, response)) = details.next().await {
+            done += 1;
+            progress.message(format!(
+                "Syncing problems {} {}/{}",
+                progress_bar(done, total, 30),
+                done,
+                total
+            ));
+            let key = CacheKey::Problem(&slug).scoped(config.profile.as_deref());
+            match response {
+                Ok(response) => {
+                    let response = Leetcode::cache_problem(&mut self.cache, key, response)?;
+                    if !response["data"]["question"].is_object() {
+                        failed.push((slug, "problem not found".to_owned()));
+                    }
+                }
+                // Every other problem would be rejected too
+                Err(e @ (LeetUpError::SessionExpired(_) | LeetUpError::LoginRequired(_))) => {
+                    progress.stop();
+                    return Err(e);
+                }
+                Err(e) => failed.push((slug, e.to_string())),
+            }
+        }
+        progress.stop();
+
+        for (slug, error) in &failed {
+            eprintln!(
+                "{}",
+                paint(Red, &format!("Failed to sync {}: {}", slug, error))
+            );
+        }
+        let mut text = paint(
+            Green,
+            &format!(
+                "Synced {} problems, {} already cached",
+                total - failed.len(),
+                free - total
+            ),
+        );
+        if !failed.is_empty() {
+            text.push_str(&format!(
+                "\n{} problems failed, run `leetup sync` again to retry them",
+                failed.len()
+            ));
+        }
+        let failed: Vec<Value> = failed
+            .iter()
+            .map(|(slug, error)| json!({"slug": slug, "error": error}))
+            .collect();
+        MessageResult::new(
+            text,
+            json!({
+                "synced": total - failed.len(),
+                "cached": free - total,
+                "failed": failed,
+            }),
+        )
+        .print_as(self.config.output);
+
+        Ok(())
+    }
+
     fn cache(&mut self) -> Result<&KvStore> {
         Ok(&self.cache)
     }
@@ -302,7 +706,7 @@ impl<'a> ServiceProvider<'a> for Leetcode<'a> {
 }
 
 impl<'a> Leetcode<'a> {
-    pub fn new(session: Option<&'a Session>, config: &'a Config, cache: KvStore) -> Result<Self> {
+    pub fn new(session: &'a LazySession, config: &'a Config, cache: KvStore) -> Result<Self> {
         let name = "leetcode";
 
         Ok(Leetcode {
@@ -310,22 +714,43 @@ impl<'a> Leetcode<'a> {
             config,
             cache,
             name,
-            remote_client: RemoteClient::new(config, session),
+            remote_client: RemoteClient::new(config, session)?,
         })
     }
 
+    /// Key of `key` in the cache of the profile in use.
+    fn cache_key(&self, key: CacheKey) -> String {
+        key.scoped(self.config.profile.as_deref())
+    }
+
     fn is_user_logged_in(&self) -> bool {
-        self.cache.has_key(CacheKey::Session.into())
+        self.cache.has_key(self.cache_key(CacheKey::Session))
     }
 
     fn cache_session(&mut self, session: Session) -> Result<()> {
-        let session_str = serde_json::to_string(&session)?;
-        self.cache.set(CacheKey::Session.into(), session_str)?;
+        let mut session_str = serde_json::to_string(&session)?;
+        match self.config.session_encryption {
+            Some(ref encryption) => session_str = service::seal(&session_str, encryption)?,
+            None => {
+                // Never downgrade an encrypted session to plain JSON
+                let cached = self.cache.get(self.cache_key(CacheKey::Session))?;
+                if cached.is_some_and(|val| service::is_sealed(&val)) {
+                    return Err(LeetUpError::Any(anyhow!(
+                        "Cached session is encrypted but `session_encryption` is not set in config.json, set it or logout first"
+                    )));
+                }
+            }
+        }
+        self.cache
+            .set(self.cache_key(CacheKey::Session), session_str)?;
         // remove key `problems`, rebuild problems cache.
         //
         // NOTE: cache.remove throws "Key not found" error
         // so ignore that error if it is thrown.
-        if self.cache.remove(CacheKey::Problems.into()).is_err() {}
+        let _ = self.cache.remove(self.cache_key(CacheKey::Problems));
+        let _ = self.cache.remove(self.cache_key(CacheKey::UserStatus));
+        // Scrub the previous session from the logs
+        self.cache.compact()?;
         Ok(())
     }
 
@@ -337,6 +762,421 @@ impl<'a> Leetcode<'a> {
         Ok(problems)
     }
 
+    /// Find a problem using its frontend ID, title slug or title.
+    ///
+    /// Fall back to a fuzzy search on titles, prompt the user to choose
+    /// if multiple problems match.
+    async fn find_problem(&mut self, key: &str) -> Result<StatStatusPair> {
+        let mut probs = self.fetch_problems().await?;
+        let key = key.trim();
+        let exact = match key.parse::<usize>() {
+            Ok(id) => probs
+                .iter()
+                .position(|item| item.stat.frontend_question_id == id),
+            Err(_) => probs.iter().position(|item| {
+                item.stat.question_title_slug == key
+                    || item.stat.question_title.eq_ignore_ascii_case(key)
+            }),
+        };
+        if let Some(index) = exact {
+            return Ok(probs.swap_remove(index));
+        }
+
+        let matches = Leetcode::fuzzy_match(&probs, key);
+        let index = match matches.as_slice() {
+            [] => return Err(LeetUpError::Any(anyhow!("Problem `{}` not found", key))),
+            [index] => *index,
+            _ => Leetcode::choose_problem(&probs, &matches)?,
+        };
+
+        Ok(probs.swap_remove(index))
+    }
+
+    /// Pick a random unsolved problem satisfying queries and tag of `pick`.
+    ///
+    /// Locked problems are skipped without premium, and so are problems
+    /// picked within the last `pick.days` days.
+    async fn random_problem(&mut self, pick: &cmd::Pick) -> Result<StatStatusPair> {
+        let mut probs = self.fetch_problems().await?;
+
+        if let Some(ref tag) = pick.tag {
+            let tag_questions = self.get_problems_with_topic_tag(tag).await?["data"]["topicTag"]
+                ["questions"]
+                .clone();
+            let slugs: HashSet<String> =
+                serde_json::from_value::<Vec<TopicTagQuestion>>(tag_questions)?
+                    .into_iter()
+                    .map(|question| question.title_slug)
+                    .collect();
+            probs.retain(|item| slugs.contains(&item.stat.question_title_slug));
+        }
+
+        let queries = pick.query.as_deref().map(Query::from_str);
+        let history = self.pick_history()?;
+        let since = service::unix_timestamp().saturating_sub(pick.days * 24 * 60 * 60);
+
+        probs.retain(|item| {
+            let recently_picked = history
+                .get(&item.stat.question_title_slug)
+                .is_some_and(|&picked_at| picked_at >= since);
+
+            !item.is_accepted()
+                && !recently_picked
+                && queries
+                    .as_ref()
+                    .is_none_or(|queries| Leetcode::apply_queries(queries, item))
+        });
+        if probs.iter().any(|item| item.paid_only) && !self.is_premium().await? {
+            probs.retain(|item| !item.paid_only);
+        }
+
+        if probs.is_empty() {
+            return Err(LeetUpError::Any(anyhow!(
+                "No unsolved problem found for the given filters"
+            )));
+        }
+        let index = rand::thread_rng().gen_range(0..probs.len());
+
+        Ok(probs.swap_remove(index))
+    }
+
+    /// Status of the user of `session`, signed out if `session` is `None`.
+    async fn user_status(&self, session: Option<&Session>) -> Result<UserStatus> {
+        if session.is_none() {
+            return Ok(UserStatus::default());
+        }
+        let body: Value = json!({
+            "query": "query globalData { userStatus { isSignedIn username isPremium } }",
+            "variables": {},
+            "operationName": "globalData"
+        });
+        let response = self
+            .remote_client
+            .post_with_session(&self.config.urls.graphql, &body, || None, session)
+            .await?;
+
+        Ok(serde_json::from_value(
+            response["data"]["userStatus"].clone(),
+        )?)
+    }
+
+    /// Check that `session` is signed in and return the name of its user.
+    async fn verify_session(&self, session: &Session) -> Result<String> {
+        let status = self.user_status(Some(session)).await?;
+        if !status.is_signed_in {
+            return Err(LeetUpError::Any(anyhow!(
+                "Session is expired or invalid, login to LeetCode in the browser and export the cookies again"
+            )));
+        }
+
+        Ok(status.username)
+    }
+
+    /// Check whether the logged in user has a premium subscription.
+    ///
+    /// The status is cached as long as the list of problems, the user is
+    /// assumed to be free if it can not be fetched.
+    async fn is_premium(&mut self) -> Result<bool> {
+        // Only free problems are synced for offline use
+        if self.config.offline {
+            return Ok(false);
+        }
+        let key = self.cache_key(CacheKey::UserStatus);
+        if let Some(ref val) = self.cache.get(key.clone())? {
+            if let Ok(status) = serde_json::from_str::<UserStatus>(val) {
+                return Ok(status.is_premium);
+            }
+        }
+
+        match self.user_status(self.session()?).await {
+            Ok(status) => {
+                let status_serialized = serde_json::to_string(&status)?;
+                match self.config.cache.problems_ttl() {
+                    Some(ttl) => self.cache.set_with_ttl(key, status_serialized, ttl)?,
+                    None => self.cache.set(key, status_serialized)?,
+                }
+                Ok(status.is_premium)
+            }
+            Err(e) => {
+                info!(
+                    "Unable to check premium status, skipping locked problems: {}",
+                    e
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Get problems picked so far with the timestamp they were last picked at.
+    fn pick_history(&mut self) -> Result<HashMap<String, u64>> {
+        match self.cache.get(self.cache_key(CacheKey::PickHistory))? {
+            Some(ref val) => Ok(serde_json::from_str(val)?),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Record `slug` as picked now.
+    ///
+    /// Problems picked more than `days` days ago are dropped.
+    fn add_pick_history(&mut self, slug: &str, days: u64) -> Result<()> {
+        let mut history = self.pick_history()?;
+        let now = service::unix_timestamp();
+        let since = now.saturating_sub(days * 24 * 60 * 60);
+        history.retain(|_, picked_at| *picked_at >= since);
+        history.insert(slug.to_owned(), now);
+        self.cache.set(
+            self.cache_key(CacheKey::PickHistory),
+            serde_json::to_string(&history)?,
+        )?;
+        Ok(())
+    }
+
+    /// Fuzzy search problems whose title or slug contain every word of `keyword`.
+    ///
+    /// Returns indices of matched problems, closest titles first.
+    fn fuzzy_match(probs: &[StatStatusPair], keyword: &str) -> Vec<usize> {
+        let words = |s: &str| -> Vec<String> {
+            s.to_lowercase()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        let keywords = words(keyword);
+        if keywords.is_empty() {
+            return vec![];
+        }
+
+        let mut matches: Vec<(usize, usize, usize)> = probs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let title = words(&item.stat.question_title).join(" ");
+                let slug = item.stat.question_title_slug.replace('-', " ");
+                let is_match = keywords
+                    .iter()
+                    .all(|w| title.contains(w.as_str()) || slug.contains(w.as_str()));
+                is_match.then_some((title.len(), item.stat.frontend_question_id, index))
+            })
+            .collect();
+        matches.sort_unstable();
+
+        matches.into_iter().map(|(_, _, index)| index).collect()
+    }
+
+    /// Prompt the user to choose a problem among multiple matches.
+    fn choose_problem(probs: &[StatStatusPair], matches: &[usize]) -> Result<usize> {
+        const MAX_CHOICES: usize = 10;
+
+        println!(
+            "{}",
+            paint(
+                Yellow,
+                &format!("Found {} matching problems:", matches.len())
+            )
+        );
+        for (i, &index) in matches.iter().take(MAX_CHOICES).enumerate() {
+            let item = &probs[index];
+            println!(
+                "{:>3}) [{:^4}] {}",
+                i + 1,
+                item.stat.frontend_question_id,
+                item.stat.question_title
+            );
+        }
+        if matches.len() > MAX_CHOICES {
+            println!("...refine your search to see other problems.");
+        }
+
+        let mut out = std::io::stdout();
+        write!(out, "{}", paint(Yellow, "Choose a problem: "))?;
+        out.flush()?;
+        let mut choice = String::new();
+        stdin().read_line(&mut choice)?;
+
+        choice
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|&choice| choice >= 1 && choice <= matches.len().min(MAX_CHOICES))
+            .map(|choice| matches[choice - 1])
+            .ok_or_else(|| LeetUpError::Any(anyhow!("Invalid choice: {}", choice.trim())))
+    }
+
+    async fn fetch_daily_slug(&self) -> Result<String> {
+        // leetcode.cn names the daily challenge `todayRecord`
+        let (field, query) = match self.config.site {
+            Site::LeetcodeCom => (
+                "activeDailyCodingChallengeQuestion",
+                r#"
+                query questionOfToday {
+                    activeDailyCodingChallengeQuestion {
+                        date
+                        question {
+                            titleSlug
+                        }
+                    }
+                }
+                "#,
+            ),
+            Site::LeetcodeCn => (
+                "todayRecord",
+                r#"
+                query questionOfToday {
+                    todayRecord {
+                        date
+                        question {
+                            titleSlug
+                        }
+                    }
+                }
+                "#,
+            ),
+        };
+        let body: Value = json!({
+            "query": query,
+            "variables": {},
+            "operationName": "questionOfToday"
+        });
+
+        let response = self
+            .remote_client
+            .post(&self.config.urls.graphql, &body, || None)
+            .await?;
+        debug!("Daily: {}", response);
+
+        let daily = match &response["data"][field] {
+            // `todayRecord` is a list
+            Value::Array(records) => records.first().cloned().unwrap_or_default(),
+            daily => daily.clone(),
+        };
+
+        daily["question"]["titleSlug"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| LeetUpError::Any(anyhow!("Daily challenge not found")))
+    }
+
+    /// Detail of the problem `slug`, from the cache unless it is stale,
+    /// was fetched with an older query, or `refresh` is set.
+    ///
+    /// A stale detail is still used if the judge can not be reached.
+    ///
+    /// Any cached detail is used offline.
+    async fn problem_detail(&mut self, slug: &str, refresh: bool) -> Result<Value> {
+        let key = self.cache_key(CacheKey::Problem(slug));
+        let cached = self.cached_problem(&key)?;
+        if self.config.offline {
+            return cached
+                .map(|cached| cached.response)
+                .ok_or_else(|| LeetUpError::NotCached(format!("Problem `{}`", slug)));
+        }
+        if let Some(ref cached) = cached {
+            if !refresh && self.is_problem_fresh(cached) {
+                debug!("Fetching problem {} from cache...", slug);
+                return Ok(cached.response.clone());
+            }
+        }
+
+        match Leetcode::fetch_problem_detail(&self.remote_client, self.config, slug).await {
+            Ok(response) => Leetcode::cache_problem(&mut self.cache, key, response),
+            Err(LeetUpError::Reqwest(e)) if !refresh && cached.is_some() => {
+                info!("Using cached problem {}: {}", slug, e);
+                Ok(cached.map(|cached| cached.response).unwrap_or_default())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Cached detail of a problem under `key`, including stale ones.
+    fn cached_problem(&mut self, key: &str) -> Result<Option<CachedQuestion>> {
+        Ok(match self.cache.get_stale(key.to_owned())? {
+            Some(ref val) => serde_json::from_str::<CachedQuestion>(val).ok(),
+            None => None,
+        })
+    }
+
+    /// Whether `cached` was fetched with the current query and is not stale.
+    fn is_problem_fresh(&self, cached: &CachedQuestion) -> bool {
+        cached.version == question_detail_version()
+            && !self
+                .config
+                .cache
+                .is_problem_stale(cached.fetched_at, service::unix_timestamp())
+    }
+
+    /// Cache the detail `response` of a problem under `key`.
+    ///
+    /// Unknown slugs have no question, they are not cached.
+    fn cache_problem(cache: &mut KvStore, key: String, response: Value) -> Result<Value> {
+        if !response["data"]["question"].is_object() {
+            return Ok(response);
+        }
+        let cached = CachedQuestion {
+            version: question_detail_version(),
+            fetched_at: service::unix_timestamp(),
+            response,
+        };
+        cache.set(key, serde_json::to_string(&cached)?)?;
+
+        Ok(cached.response)
+    }
+
+    async fn fetch_problem_detail(
+        remote_client: &RemoteClient<'_>,
+        config: &Config,
+        slug: &str,
+    ) -> Result<Value> {
+        let body: Value = json!({
+            "query": QUESTION_DETAIL_QUERY,
+            "variables": json!({
+                "titleSlug": slug,
+            }),
+            "operationName": "getQuestionDetail"
+        });
+
+        let mut response = remote_client
+            .post(&config.urls.graphql, &body, || None)
+            .await?;
+        debug!("Response: {}", response);
+
+        // Prefer translated content wherever it is available
+        if config.site.is_translated() {
+            let question = &mut response["data"]["question"];
+            for (field, translated) in [
+                ("content", "translatedContent"),
+                ("title", "translatedTitle"),
+            ] {
+                if question[translated].is_string() {
+                    question[field] = question[translated].take();
+                }
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Scrape the code of a submission from its detail page.
+    async fn fetch_submission_code(&self, id: u64) -> Result<String> {
+        let url = self.config.urls.submission.replace("$id", &id.to_string());
+        let page = self
+            .remote_client
+            .get(&url, None, self.session()?)
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let re = Regex::new(r"submissionCode:\s*'((?:[^'\\]|\\.)*)'")?;
+        let code = re
+            .captures(&page)
+            .and_then(|caps| caps.get(1))
+            .ok_or_else(|| LeetUpError::Any(anyhow!("Code not found for submission {}", id)))?;
+
+        // The code is an escaped JS string e.g. `\u000A` for a new line.
+        let code = format!("\"{}\"", code.as_str().replace("\\'", "'"));
+        Ok(serde_json::from_str(&code)?)
+    }
+
     async fn run_code(&self, url: &str, problem: &Problem, body: Value) -> Result<Value> {
         let url = url.replace("$slug", &problem.slug);
         self.remote_client
@@ -351,18 +1191,49 @@ impl<'a> Leetcode<'a> {
             .await
     }
 
-    async fn verify_run_code(&self, url: &str) -> Result<Value> {
+    /// Wait for the judge result, giving up on timeout or Ctrl-C.
+    async fn verify_run_code(&self, url: &str, spinner: &Progress) -> Result<Value> {
+        let judge = &self.config.judge;
+        tokio::select! {
+            result = tokio::time::timeout(judge.timeout(), self.poll_judge(url, spinner)) => {
+                result.map_err(|_| {
+                    LeetUpError::Any(anyhow!(
+                        "Timed out after {}s waiting for judge result",
+                        judge.timeout
+                    ))
+                })?
+            }
+            _ = tokio::signal::ctrl_c() => {
+                Err(LeetUpError::Any(anyhow!("Cancelled waiting for judge result")))
+            }
+        }
+    }
+
+    async fn poll_judge(&self, url: &str, spinner: &Progress) -> Result<Value> {
+        let judge = &self.config.judge;
+        let mut backoff = Backoff::new(judge.poll_interval(), judge.max_poll_interval());
         loop {
-            let response = self
-                .remote_client
-                .get(url, None, self.session())
-                .await?
-                .json::<Value>()
-                .await?;
-            if response["state"] == "SUCCESS" {
-                return Ok(response);
+            let delay = backoff.next().unwrap_or_default();
+            let response = self.remote_client.get(url, None, self.session()?).await?;
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let delay = retry_after(&response).unwrap_or(delay);
+                debug!("Rate limited, next poll in {:?}", delay);
+                spinner.message(format!("Rate limited, retrying in {}s", delay.as_secs()));
+                tokio::time::sleep(delay).await;
+                continue;
             }
-            std::thread::sleep(std::time::Duration::from_millis(200));
+
+            let response = response.error_for_status()?.json::<Value>().await?;
+            match JudgeState::from(&response) {
+                JudgeState::Success => return Ok(response),
+                state @ JudgeState::Failure(_) => {
+                    return Err(LeetUpError::Any(anyhow!("Judge {}", state)));
+                }
+                state => {
+                    spinner.message(state.to_string());
+                }
+            }
+            tokio::time::sleep(delay).await;
         }
     }
 
@@ -374,11 +1245,8 @@ impl<'a> Leetcode<'a> {
         pos: InjectPosition,
     ) -> Result<()> {
         if let Some(either) = code_fragment {
-            let inject_code_pos_pattern = format!(
-                "\n{} {}\n",
-                comment,
-                Pattern::InjectCodePosition(pos).to_string()
-            );
+            let inject_code_pos_pattern =
+                format!("\n{} {}\n", comment, Pattern::InjectCodePosition(pos));
             buf.push_str(&inject_code_pos_pattern);
             let code_fragment = either.to_string();
             buf.push_str(&code_fragment);
@@ -387,13 +1255,20 @@ impl<'a> Leetcode<'a> {
         Ok(())
     }
 
-    fn logout(&mut self) -> Result<()> {
-        if self.cache.remove(CacheKey::Session.into()).is_err() {
-            println!("User not logged in!");
-            return Ok(());
+    /// Remove the cached session, returns whether the user was logged in.
+    fn logout(&mut self) -> Result<bool> {
+        if self
+            .cache
+            .remove(self.cache_key(CacheKey::Session))
+            .is_err()
+        {
+            return Ok(false);
         }
-        if self.cache.remove(CacheKey::Problems.into()).is_err() {}
-        Ok(())
+        let _ = self.cache.remove(self.cache_key(CacheKey::Problems));
+        let _ = self.cache.remove(self.cache_key(CacheKey::UserStatus));
+        // Scrub the removed session from the logs
+        self.cache.compact()?;
+        Ok(true)
     }
 
     fn execute_script(&self, cmd: &str, problem: &Problem, dir: &Path) -> Result<()> {
@@ -410,53 +1285,87 @@ impl<'a> Leetcode<'a> {
     fn pick_hook(&self, content: &str, problem: &Problem, lang: &LangInfo) -> Result<()> {
         let mut curr_dir = env::current_dir()?;
         let mut filename = curr_dir.clone();
+        let mut text = String::new();
+        let mut created_project = None;
         let cfg = self.config()?;
-        if let Some(ref cfg) = cfg.pick_hook {
-            if let Some(hook_cfg) = cfg.get(&lang.name) {
-                if let Some(dir) = hook_cfg.working_dir() {
-                    let dir = shellexpand::tilde(dir);
-                    curr_dir = PathBuf::from(dir.deref());
-                    fs::create_dir_all(&curr_dir)?;
-                    filename = curr_dir.clone();
-                }
-                if let Some(pre) = hook_cfg.script_pre_generation() {
-                    println!(
-                        "{}",
-                        Color::Cyan("Executing pre-generation script...").make()
-                    );
-                    let cmd = pre.to_string();
-                    self.execute_script(&cmd, problem, &curr_dir)?;
-                }
-                self.write_content(&mut filename, problem, lang, content.as_bytes())?;
-
-                if let Some(post) = hook_cfg.script_post_generation() {
-                    println!(
-                        "{}",
-                        Color::Cyan("Executing post-generation script...").make()
-                    );
-                    let cmd = post.to_string();
-                    self.execute_script(&cmd, problem, &curr_dir)?;
+        if let Some(hook_cfg) = cfg.pick_hook.as_ref().and_then(|cfg| cfg.get(&lang.name)) {
+            if let Some(dir) = hook_cfg.working_dir() {
+                let dir = shellexpand::tilde(dir);
+                curr_dir = PathBuf::from(dir.deref());
+                fs::create_dir_all(&curr_dir)?;
+                filename = curr_dir.clone();
+            }
+            if let Some(ProjectLayout::Cargo) = self.project_layout(lang)? {
+                let project = CargoProject::new(&curr_dir);
+                if project.init()? {
+                    let dir = curr_dir.to_str().ok_or(LeetUpError::OptNone)?;
+                    text.push_str(&paint(Cyan, &format!("Created Cargo project: {}", dir)));
+                    text.push('\n');
+                    created_project = Some(dir.to_owned());
                 }
+                filename = project.problems_dir();
+            }
+            if let Some(pre) = hook_cfg.script_pre_generation() {
+                self.progress_message("Executing pre-generation script...");
+                let cmd = pre.to_string();
+                self.execute_script(&cmd, problem, &curr_dir)?;
+            }
+            self.write_content(&mut filename, problem, lang, content.as_bytes())?;
 
-                // File path can be wrong if you used: `mkdir`, `cd`, `mv` to move
-                // around the generated file. Find the right path used in your script!
-                println!(
-                    "Generated: {}\n{}",
-                    Color::Magenta(filename.to_str().ok_or(LeetUpError::OptNone)?).make(),
-                    Color::Yellow("Note: File path can be wrong if you used: `mkdir`, `cd`, `mv` to move around the generated file. Find the right path used in your script!").make()
-                );
-                return Ok(());
+            if let Some(post) = hook_cfg.script_post_generation() {
+                self.progress_message("Executing post-generation script...");
+                let cmd = post.to_string();
+                self.execute_script(&cmd, problem, &curr_dir)?;
             }
+        } else {
+            self.write_content(&mut filename, problem, lang, content.as_bytes())?;
         }
-        self.write_content(&mut filename, problem, lang, content.as_bytes())?;
-        println!(
-            "Generated: {}",
-            Color::Magenta(filename.to_str().ok_or(LeetUpError::OptNone)?).make()
-        );
+
+        let generated = filename.to_str().ok_or(LeetUpError::OptNone)?;
+        text.push_str(&format!("Generated: {}", paint(Purple, generated)));
+        if cfg
+            .pick_hook
+            .as_ref()
+            .is_some_and(|cfg| cfg.contains_key(&lang.name))
+        {
+            // File path can be wrong if you used: `mkdir`, `cd`, `mv` to move
+            // around the generated file. Find the right path used in your script!
+            text.push('\n');
+            text.push_str(&paint(Yellow, "Note: File path can be wrong if you used: `mkdir`, `cd`, `mv` to move around the generated file. Find the right path used in your script!"));
+        }
+        MessageResult::new(
+            text,
+            json!({"generated": generated, "created_project": created_project}),
+        )
+        .print_as(self.config.output);
 
         Ok(())
     }
 
+    /// Print progress of a command, skipped with `--output json`.
+    fn progress_message(&self, message: &str) {
+        if self.config.output != OutputFormat::Json {
+            println!("{}", paint(Cyan, message));
+        }
+    }
+
+    /// Project layout of `pick_hook` for `lang`, only Rust supports a Cargo project.
+    fn project_layout(&self, lang: &LangInfo) -> Result<Option<ProjectLayout>> {
+        let layout = self
+            .config()?
+            .pick_hook
+            .as_ref()
+            .and_then(|cfg| cfg.get(&lang.name))
+            .and_then(|hook_cfg| hook_cfg.project_layout());
+        match layout {
+            Some(ProjectLayout::Cargo) if lang.name != "rust" => Err(LeetUpError::Any(anyhow!(
+                "`project_layout` cargo is only supported for rust, not {}",
+                lang.name
+            ))),
+            layout => Ok(layout),
+        }
+    }
+
     fn write_content(
         &self,
         filename: &mut PathBuf,
@@ -541,7 +1450,7 @@ impl<'a> Leetcode<'a> {
         };
 
         if let Some(content) = &response["data"]["question"]["content"].as_str() {
-            let content = from_read(content.as_bytes(), 80);
+            let content = from_read(normalize_html(content).as_bytes(), 80);
             let content = content.replace("**", "");
             let content = content
                 .split('\n')
@@ -550,9 +1459,8 @@ impl<'a> Leetcode<'a> {
                 .join("\n");
             info!("Single Comment: {}", single_comment);
 
-            let pattern_custom = format!("{} {}", single_comment, Pattern::CustomCode.to_string());
-            let pattern_leetup_info =
-                format!("{} {}", single_comment, Pattern::LeetUpInfo.to_string());
+            let pattern_custom = format!("{} {}", single_comment, Pattern::CustomCode);
+            let pattern_leetup_info = format!("{} {}", single_comment, Pattern::LeetUpInfo);
             let content = format!(
                 "{}\n{} id={} lang={} slug={}\n\n{}\n{}\n{}\n{}",
                 pattern_custom,
@@ -578,16 +1486,21 @@ impl<'a> Leetcode<'a> {
             let code_defs: HashMap<_, _> = serde_json::from_str::<Vec<CodeDefinition>>(code_defs)?
                 .into_iter()
                 .map(|def| (def.value.to_owned(), def))
-                .into_iter()
                 .collect();
             if let Some(ref definition) = definition {
                 buf.push_str(definition)
             }
-            let pattern_code = format!("\n{} {}\n", single_comment, Pattern::Code.to_string());
-            let code = &code_defs
-                .get(&lang.name)
-                .ok_or(LeetUpError::OptNone)?
-                .default_code;
+            let pattern_code = format!("\n{} {}\n", single_comment, Pattern::Code);
+            // Use typed code of the problem if available e.g. a downloaded submission
+            let code = match problem.typed_code {
+                Some(ref typed_code) => typed_code,
+                None => {
+                    &code_defs
+                        .get(&lang.name)
+                        .ok_or(LeetUpError::OptNone)?
+                        .default_code
+                }
+            };
             debug!("Code: {}", code);
             let inject_code = self
                 .config()?
@@ -603,6 +1516,33 @@ impl<'a> Leetcode<'a> {
                     InjectPosition::BeforeCodeExclude,
                 )?;
             }
+            // Definitions of data structures only given as comments in the code
+            let mut helper = helper_code(&lang.name, HelperTypes::detect(code));
+            let mut has_solution_struct = inject_code.is_some_and(declares_solution_struct);
+            // Each problem of a Cargo project is a crate which needs its own `Solution`
+            if self.project_layout(lang)? == Some(ProjectLayout::Cargo) && !has_solution_struct {
+                let helper = helper.get_or_insert_with(String::new);
+                if !helper.is_empty() {
+                    helper.push_str("\n\n");
+                }
+                helper.push_str("struct Solution;");
+                has_solution_struct = true;
+            }
+            self.write_code_fragment(
+                &mut buf,
+                single_comment,
+                helper.map(Either::String).as_ref(),
+                InjectPosition::BeforeCodeExclude,
+            )?;
+            let test_scaffold = self.test_scaffold(lang, response, has_solution_struct);
+            if let Some(ref scaffold) = test_scaffold {
+                self.write_code_fragment(
+                    &mut buf,
+                    single_comment,
+                    scaffold.before_code.clone().map(Either::String).as_ref(),
+                    InjectPosition::Tests,
+                )?;
+            }
             buf.push_str(&pattern_code);
             if let Some(inject_code) = inject_code {
                 self.write_code_fragment(
@@ -623,6 +1563,14 @@ impl<'a> Leetcode<'a> {
                     InjectPosition::AfterCode,
                 )?;
             }
+            if let Some(scaffold) = test_scaffold {
+                self.write_code_fragment(
+                    &mut buf,
+                    single_comment,
+                    Some(&Either::String(scaffold.after_code)),
+                    InjectPosition::Tests,
+                )?;
+            }
 
             self.pick_hook(&buf, problem, lang)?;
         }
@@ -630,6 +1578,26 @@ impl<'a> Leetcode<'a> {
         Ok(())
     }
 
+    /// Unit tests for the examples of a problem, if its signature and language are supported.
+    fn test_scaffold(
+        &self,
+        lang: &LangInfo,
+        response: &Value,
+        has_solution_struct: bool,
+    ) -> Option<TestScaffold> {
+        let question: QuestionDetail =
+            serde_json::from_value(response["data"]["question"].clone()).ok()?;
+        let signature = Signature::try_from(&question.meta_data()?).ok()?;
+
+        match TestScaffold::new(&lang.name, &question, &signature, has_solution_struct) {
+            Ok(scaffold) => scaffold,
+            Err(e) => {
+                debug!("Skipping test scaffold: {}", e);
+                None
+            }
+        }
+    }
+
     /*
      * Parse Option<Option<String>> from structopt
      *
@@ -657,3 +1625,60 @@ impl<'a> Leetcode<'a> {
         })
     }
 }
+
+/// Whether one of the fragments injected around the code already declares `struct Solution`.
+fn declares_solution_struct(inject_code: &InjectCode) -> bool {
+    [
+        &inject_code.before_code,
+        &inject_code.before_code_exclude,
+        &inject_code.after_code,
+    ]
+    .iter()
+    .any(|fragment| {
+        fragment
+            .as_ref()
+            .is_some_and(|f| f.to_string().contains("struct Solution"))
+    })
+}
+
+#[test]
+fn test_fuzzy_match() {
+    let pair = |id: usize, title: &str, slug: &str| {
+        serde_json::json!({
+            "stat": {
+                "question_id": id,
+                "question__title": title,
+                "question__title_slug": slug,
+                "question__hide": false,
+                "total_acs": 0,
+                "total_submitted": 0,
+                "frontend_question_id": id,
+                "is_new_question": false
+            },
+            "status": null,
+            "difficulty": {"level": 1},
+            "paid_only": false,
+            "is_favor": false,
+            "frequency": 0,
+            "progress": 0
+        })
+    };
+    let probs: Vec<StatStatusPair> = serde_json::from_value(serde_json::json!([
+        pair(
+            167,
+            "Two Sum II - Input Array Is Sorted",
+            "two-sum-ii-input-array-is-sorted"
+        ),
+        pair(1, "Two Sum", "two-sum"),
+        pair(15, "3Sum", "3sum"),
+        pair(2, "Add Two Numbers", "add-two-numbers"),
+    ]))
+    .unwrap();
+
+    assert_eq!(Leetcode::fuzzy_match(&probs, "two sum"), vec![1, 0]);
+    assert_eq!(Leetcode::fuzzy_match(&probs, "SUM ii"), vec![0]);
+    assert_eq!(Leetcode::fuzzy_match(&probs, "3sum"), vec![2]);
+    assert_eq!(Leetcode::fuzzy_match(&probs, "add-two"), vec![3]);
+    assert!(Leetcode::fuzzy_match(&probs, "median").is_empty());
+    assert!(Leetcode::fuzzy_match(&probs, " - ").is_empty());
+}
@@ -1,12 +1,26 @@
+pub use date::*;
 pub use file::*;
+pub use helper::*;
 pub use lang::*;
+pub use project::*;
 pub use provider::*;
+pub use runner::*;
+pub use scaffold::*;
+pub use secret::*;
 pub use session::*;
 
+pub(crate) use judge::{retry_after, Backoff};
+
 pub mod auth;
+mod date;
 mod file;
+mod helper;
+mod judge;
 mod lang;
 pub mod leetcode;
-mod pool;
+mod project;
 mod provider;
+mod runner;
+mod scaffold;
+mod secret;
 mod session;
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Result;
+
+/// Cargo package holding picked Rust problems as `tests/<slug>.rs` targets.
+///
+/// Cargo discovers every file in `tests/` as its own crate, so solutions build without a `main`,
+/// run with `cargo test --test <slug>` and rust-analyzer sees all of them. Unlike a workspace
+/// member or a `[[bin]]` per problem, picking a problem never has to edit the manifest.
+#[derive(Debug)]
+pub struct CargoProject {
+    root: PathBuf,
+}
+
+impl CargoProject {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        CargoProject {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Create `Cargo.toml` and `tests/` unless they exist, returns whether the manifest was created.
+    pub fn init(&self) -> Result<bool> {
+        fs::create_dir_all(self.problems_dir())?;
+        let manifest = self.root.join("Cargo.toml");
+        if manifest.exists() {
+            return Ok(false);
+        }
+
+        let content = format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+            package_name(&self.root)
+        );
+        fs::write(manifest, content)?;
+
+        Ok(true)
+    }
+
+    /// Directory of the generated problems.
+    pub fn problems_dir(&self) -> PathBuf {
+        self.root.join("tests")
+    }
+}
+
+/// Package name derived from the project directory, e.g. `~/lc/Rust` to `rust`.
+fn package_name(root: &Path) -> String {
+    let name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+
+    match name.chars().next() {
+        None => "leetcode".to_owned(),
+        Some(c) if c.is_ascii_digit() => format!("leetcode-{}", name),
+        Some(_) => name.to_owned(),
+    }
+}
+
+#[test]
+fn test_cargo_project() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("My LC");
+    let project = CargoProject::new(&root);
+
+    assert!(project.init().unwrap());
+    assert!(project.problems_dir().is_dir());
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    assert!(manifest.starts_with("[package]\nname = \"my-lc\"\n"));
+
+    // An existing manifest is left alone
+    fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+    assert!(!project.init().unwrap());
+    assert_eq!(
+        fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+        "[workspace]\n"
+    );
+
+    assert_eq!(package_name(Path::new("/lc/2024")), "leetcode-2024");
+    assert_eq!(package_name(Path::new("/")), "leetcode");
+}
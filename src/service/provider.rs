@@ -1,6 +1,5 @@
 use std::cmp::Ordering;
 
-use ansi_term::Colour::{Green, Red, Yellow};
 use async_trait::async_trait;
 use leetup_cache::kvstore::KvStore;
 
@@ -9,7 +8,6 @@ use crate::model::{DifficultyType, ProblemInfo};
 use crate::service::Session;
 use crate::{
     cmd::{self, OrderBy, Query, User},
-    icon::Icon,
     Config, Result,
 };
 
@@ -17,64 +15,30 @@ use crate::{
 /// on any type of Online Judge through leetup CLI.
 #[async_trait]
 pub trait ServiceProvider<'a> {
-    fn session(&self) -> Option<&Session>;
+    fn session(&self) -> Result<Option<&Session>>;
     fn config(&self) -> Result<&Config>;
     async fn fetch_all_problems(&mut self) -> Result<serde_json::value::Value>;
     async fn list_problems(&mut self, list: cmd::List) -> Result<()>;
     async fn pick_problem(&mut self, pick: cmd::Pick) -> Result<()>;
+    async fn show_problem(&mut self, show: cmd::Show) -> Result<()>;
+    async fn daily_problem(&mut self, daily: cmd::Daily) -> Result<()>;
     async fn problem_test(&self, test: cmd::Test) -> Result<()>;
     async fn problem_submit(&self, submit: cmd::Submit) -> Result<()>;
+    async fn problem_run(&mut self, run: cmd::Run) -> Result<()>;
+    async fn problem_submissions(&mut self, submissions: cmd::Submissions) -> Result<()>;
     async fn process_auth(&mut self, user: User) -> Result<()>;
+    async fn process_cache(&mut self, cache: cmd::Cache) -> Result<()>;
+    async fn sync_problems(&mut self, sync: cmd::Sync) -> Result<()>;
+    #[allow(dead_code)]
     fn cache(&mut self) -> Result<&KvStore>;
+    #[allow(dead_code)]
     fn name(&self) -> &'a str;
 
-    /// Print list of problems properly.
-    fn pretty_list<T: IntoIterator<Item = &'a Box<dyn ProblemInfo + Send>>>(probs: T) {
-        for prob in probs {
-            let is_favorite = if let Some(is_favor) = prob.is_favorite() {
-                is_favor
-            } else {
-                false
-            };
-            let starred_icon = if is_favorite {
-                Yellow.paint(Icon::Star.to_string()).to_string()
-            } else {
-                Icon::Empty.to_string()
-            };
-
-            let locked_icon = if prob.is_paid_only() {
-                Red.paint(Icon::Lock.to_string()).to_string()
-            } else {
-                Icon::Empty.to_string()
-            };
-
-            let acd = if prob.status().is_some() {
-                Green.paint(Icon::Yes.to_string()).to_string()
-            } else {
-                Icon::Empty.to_string()
-            };
-
-            println!(
-                "{} {:2} {} [{:^4}] {:75} {:6}",
-                starred_icon,
-                locked_icon,
-                acd,
-                prob.question_id(),
-                prob.question_title(),
-                prob.difficulty().to_string()
-            );
-        }
-    }
-
     /// Filter problems using multiple queries.
-    fn apply_queries(queries: &Vec<Query>, o: &Box<dyn ProblemInfo + Send>) -> bool {
+    fn apply_queries(queries: &[Query], o: &(dyn ProblemInfo + Send)) -> bool {
         let mut is_satisfied = true;
         let difficulty: DifficultyType = o.difficulty().into();
-        let is_favorite = if let Some(is_favor) = o.is_favorite() {
-            is_favor
-        } else {
-            false
-        };
+        let is_favorite = o.is_favorite().unwrap_or_default();
 
         for q in queries {
             match q {
@@ -86,8 +50,8 @@ pub trait ServiceProvider<'a> {
                 Query::NotHard => is_satisfied &= difficulty != Hard,
                 Query::Locked => is_satisfied &= o.is_paid_only(),
                 Query::Unlocked => is_satisfied &= !o.is_paid_only(),
-                Query::Done => is_satisfied &= o.status().is_some(),
-                Query::NotDone => is_satisfied &= o.status().is_none(),
+                Query::Done => is_satisfied &= o.is_accepted(),
+                Query::NotDone => is_satisfied &= !o.is_accepted(),
                 Query::Starred => is_satisfied &= is_favorite,
                 Query::Unstarred => is_satisfied &= !is_favorite,
             }
@@ -99,12 +63,12 @@ pub trait ServiceProvider<'a> {
     /// Order problems by Id, Title, Difficulty in Ascending or Descending order
     fn with_ordering(
         orders: &[OrderBy],
-        a: &Box<dyn ProblemInfo + Send>,
-        b: &Box<dyn ProblemInfo + Send>,
+        a: &(dyn ProblemInfo + Send),
+        b: &(dyn ProblemInfo + Send),
     ) -> Ordering {
         let mut ordering = Ordering::Equal;
         let id_ordering = a.question_id().cmp(&b.question_id());
-        let title_ordering = a.question_title().cmp(&b.question_title());
+        let title_ordering = a.question_title().cmp(b.question_title());
         let a_difficulty_level: DifficultyType = a.difficulty().into();
         let b_difficulty_level: DifficultyType = b.difficulty().into();
         let diff_ordering = a_difficulty_level.cmp(&b_difficulty_level);
@@ -128,14 +92,51 @@ pub enum CacheKey<'a> {
     Session,
     Problems,
     Problem(&'a str),
+    Daily,
+    PickHistory,
+    UserStatus,
 }
 
-impl<'a> From<CacheKey<'_>> for String {
+impl CacheKey<'_> {
+    /// Key of the entry in the cache of `profile`, the default profile uses the bare key.
+    pub fn scoped(self, profile: Option<&str>) -> String {
+        let key: String = self.into();
+        match profile {
+            Some(profile) => format!("{}:{}", profile, key),
+            None => key,
+        }
+    }
+
+    /// Inverse of `scoped`, `None` if `key` belongs to another profile.
+    pub fn unscoped<'k>(key: &'k str, profile: Option<&str>) -> Option<&'k str> {
+        match profile {
+            Some(profile) => key.strip_prefix(profile)?.strip_prefix(':'),
+            None => (!key.contains(':')).then_some(key),
+        }
+    }
+}
+
+impl From<CacheKey<'_>> for String {
     fn from(key: CacheKey) -> Self {
         match key {
             CacheKey::Session => "session".to_string(),
             CacheKey::Problems => "problems".to_string(),
             CacheKey::Problem(id) => format!("problem_{}", id),
+            CacheKey::Daily => "daily".to_string(),
+            CacheKey::PickHistory => "pick_history".to_string(),
+            CacheKey::UserStatus => "user_status".to_string(),
         }
     }
 }
+
+#[test]
+fn test_cache_key_scoped() {
+    assert_eq!(CacheKey::Session.scoped(None), "session");
+    assert_eq!(CacheKey::Problems.scoped(Some("work")), "work:problems");
+    assert_eq!(
+        CacheKey::unscoped("work:problems", Some("work")),
+        Some("problems")
+    );
+    assert_eq!(CacheKey::unscoped("work:problems", None), None);
+    assert_eq!(CacheKey::unscoped("problems", None), Some("problems"));
+}
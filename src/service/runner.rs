@@ -0,0 +1,873 @@
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::str::FromStr;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::model::MetaData;
+use crate::service::{helper_code, HelperTypes, Lang};
+use crate::{LeetUpError, Result};
+
+/// Prefix of the lines where a harness prints the result of a test case.
+const OUTPUT_MARKER: &str = "@leetup=output ";
+
+/// Delay between checks whether a running solution exited.
+const WAIT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Types of parameters and return values found in `metaData`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueType {
+    Integer,
+    Long,
+    Double,
+    Boolean,
+    String,
+    Character,
+    Void,
+    ListNode,
+    TreeNode,
+    List(Box<ValueType>),
+}
+
+impl FromStr for ValueType {
+    type Err = LeetUpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(inner) = s.strip_suffix("[]") {
+            return Ok(ValueType::List(Box::new(ValueType::from_str(inner)?)));
+        }
+        if let Some(inner) = s.strip_prefix("list<").and_then(|s| s.strip_suffix('>')) {
+            return Ok(ValueType::List(Box::new(ValueType::from_str(inner)?)));
+        }
+
+        match s {
+            "integer" => Ok(ValueType::Integer),
+            "long" => Ok(ValueType::Long),
+            "double" => Ok(ValueType::Double),
+            "boolean" => Ok(ValueType::Boolean),
+            "string" => Ok(ValueType::String),
+            "character" => Ok(ValueType::Character),
+            "void" => Ok(ValueType::Void),
+            "ListNode" => Ok(ValueType::ListNode),
+            "TreeNode" => Ok(ValueType::TreeNode),
+            _ => Err(LeetUpError::Any(anyhow!(
+                "Type `{}` is not supported to run locally",
+                s
+            ))),
+        }
+    }
+}
+
+impl ValueType {
+    pub fn rust_type(&self) -> String {
+        match self {
+            ValueType::Integer => "i32".into(),
+            ValueType::Long => "i64".into(),
+            ValueType::Double => "f64".into(),
+            ValueType::Boolean => "bool".into(),
+            ValueType::String => "String".into(),
+            ValueType::Character => "char".into(),
+            ValueType::Void => "()".into(),
+            ValueType::ListNode => "Option<Box<ListNode>>".into(),
+            ValueType::TreeNode => "Option<std::rc::Rc<std::cell::RefCell<TreeNode>>>".into(),
+            ValueType::List(inner) => format!("Vec<{}>", inner.rust_type()),
+        }
+    }
+
+    pub fn cpp_type(&self) -> String {
+        match self {
+            ValueType::Integer => "int".into(),
+            ValueType::Long => "long long".into(),
+            ValueType::Double => "double".into(),
+            ValueType::Boolean => "bool".into(),
+            ValueType::String => "string".into(),
+            ValueType::Character => "char".into(),
+            ValueType::Void => "void".into(),
+            ValueType::ListNode => "ListNode*".into(),
+            ValueType::TreeNode => "TreeNode*".into(),
+            ValueType::List(inner) => format!("vector<{}>", inner.cpp_type()),
+        }
+    }
+
+    /// Rust expression of a JSON encoded value.
+    pub fn rust_literal(&self, value: &Value) -> Result<String> {
+        let literal = match (self, value) {
+            (ValueType::Integer | ValueType::Long, Value::Number(n)) if n.is_i64() => n.to_string(),
+            (ValueType::Double, Value::Number(n)) => format!("{:?}", n.as_f64().unwrap_or(0.0)),
+            (ValueType::Boolean, Value::Bool(b)) => b.to_string(),
+            (ValueType::String, Value::String(s)) => format!("{:?}.to_string()", s),
+            (ValueType::Character, Value::String(s)) if s.chars().count() == 1 => {
+                format!("{:?}", s.chars().next().unwrap_or_default())
+            }
+            (ValueType::ListNode, Value::Array(_)) => format!(
+                "to_list({})",
+                ValueType::List(Box::new(ValueType::Integer)).rust_literal(value)?
+            ),
+            (ValueType::TreeNode, Value::Array(values)) => format!(
+                "to_tree(vec![{}])",
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Null => Ok("None".to_owned()),
+                        _ => Ok(format!("Some({})", ValueType::Integer.rust_literal(v)?)),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            (ValueType::List(inner), Value::Array(values)) => format!(
+                "vec![{}]",
+                values
+                    .iter()
+                    .map(|v| inner.rust_literal(v))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            _ => return Err(unexpected_value(self, value)),
+        };
+
+        Ok(literal)
+    }
+
+    /// C++ initializer of a JSON encoded value.
+    pub fn cpp_literal(&self, value: &Value) -> Result<String> {
+        let literal = match (self, value) {
+            (ValueType::Integer, Value::Number(n)) if n.is_i64() => n.to_string(),
+            (ValueType::Long, Value::Number(n)) if n.is_i64() => format!("{}LL", n),
+            (ValueType::Double, Value::Number(n)) => format!("{:?}", n.as_f64().unwrap_or(0.0)),
+            (ValueType::Boolean, Value::Bool(b)) => b.to_string(),
+            (ValueType::String, Value::String(_)) => value.to_string(),
+            (ValueType::Character, Value::String(s)) if s.chars().count() == 1 => {
+                match s.as_str() {
+                    "'" | "\\" => format!("'\\{}'", s),
+                    _ => format!("'{}'", s),
+                }
+            }
+            (ValueType::ListNode, Value::Array(_)) => format!(
+                "to_list({})",
+                ValueType::List(Box::new(ValueType::Integer)).cpp_literal(value)?
+            ),
+            (ValueType::TreeNode, Value::Array(values)) => format!(
+                "to_tree({{{}}})",
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Null => Ok("nullopt".to_owned()),
+                        _ => ValueType::Integer.cpp_literal(v),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            (ValueType::List(inner), Value::Array(values)) => format!(
+                "{{{}}}",
+                values
+                    .iter()
+                    .map(|v| inner.cpp_literal(v))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            _ => return Err(unexpected_value(self, value)),
+        };
+
+        Ok(literal)
+    }
+}
+
+impl ValueType {
+    /// Python expression of a JSON encoded value.
+    pub fn python_literal(&self, value: &Value) -> Result<String> {
+        let literal = match (self, value) {
+            (ValueType::ListNode, Value::Array(_)) => format!("to_list({})", python_value(value)),
+            (ValueType::TreeNode, Value::Array(_)) => format!("to_tree({})", python_value(value)),
+            (ValueType::List(inner), Value::Array(values)) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(|v| inner.python_literal(v))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            (ValueType::ListNode | ValueType::TreeNode, _) => {
+                return Err(unexpected_value(self, value))
+            }
+            _ => python_value(value),
+        };
+
+        Ok(literal)
+    }
+
+    /// Python expression converting `expr` of this type to plain values comparable to JSON.
+    pub fn python_values(&self, expr: &str) -> String {
+        match self {
+            ValueType::ListNode => format!("list_values({})", expr),
+            ValueType::TreeNode => format!("tree_values({})", expr),
+            ValueType::List(inner) if inner.is_node() => {
+                format!("[{} for v in {}]", inner.python_values("v"), expr)
+            }
+            _ => expr.to_owned(),
+        }
+    }
+
+    fn is_node(&self) -> bool {
+        match self {
+            ValueType::ListNode | ValueType::TreeNode => true,
+            ValueType::List(inner) => inner.is_node(),
+            _ => false,
+        }
+    }
+}
+
+/// Python expression of a JSON value.
+pub fn python_value(value: &Value) -> String {
+    match value {
+        Value::Null => "None".into(),
+        Value::Bool(true) => "True".into(),
+        Value::Bool(false) => "False".into(),
+        Value::Number(n) => n.to_string(),
+        Value::String(_) => value.to_string(),
+        Value::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(python_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(k, v)| format!("{}: {}", Value::String(k.to_owned()), python_value(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn unexpected_value(value_type: &ValueType, value: &Value) -> LeetUpError {
+    LeetUpError::Any(anyhow!(
+        "Expected a value of type `{:?}`, found `{}`",
+        value_type,
+        value
+    ))
+}
+
+/// Convert a `metaData` function name to snake case, e.g. `isValidBST` to `is_valid_bst`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// Function signature of a solution.
+#[derive(Debug)]
+pub struct Signature {
+    pub name: String,
+    pub params: Vec<(String, ValueType)>,
+    pub return_type: ValueType,
+}
+
+impl TryFrom<&MetaData> for Signature {
+    type Error = LeetUpError;
+
+    fn try_from(meta: &MetaData) -> Result<Self> {
+        let name = meta.name.to_owned().ok_or_else(|| {
+            LeetUpError::Any(anyhow!("Design problems are not supported to run locally"))
+        })?;
+        let params = meta
+            .params
+            .iter()
+            .map(|p| Ok((p.name.to_owned(), ValueType::from_str(&p.param_type)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let return_type = match meta.return_type {
+            Some(ref r) => ValueType::from_str(&r.return_type)?,
+            None => ValueType::Void,
+        };
+
+        Ok(Signature {
+            name,
+            params,
+            return_type,
+        })
+    }
+}
+
+impl Signature {
+    /// Split test data, one JSON value per line, into test cases.
+    pub fn test_cases(&self, test_data: &str) -> Result<Vec<Vec<Value>>> {
+        let lines: Vec<&str> = test_data
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if self.params.is_empty() || !lines.len().is_multiple_of(self.params.len()) {
+            return Err(LeetUpError::Any(anyhow!(
+                "Expected {} line(s) per test case, found {} line(s)",
+                self.params.len(),
+                lines.len()
+            )));
+        }
+
+        lines
+            .chunks(self.params.len())
+            .map(|case| {
+                case.iter()
+                    .map(|line| serde_json::from_str(line).map_err(LeetUpError::Serde))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// `void` functions modify their first parameter in-place.
+    fn is_in_place(&self) -> bool {
+        self.return_type == ValueType::Void
+    }
+}
+
+/// Generated program running a solution against test cases.
+#[derive(Debug)]
+pub struct Harness {
+    filename: &'static str,
+    source: String,
+    build: Option<Vec<&'static str>>,
+    run: Vec<&'static str>,
+}
+
+/// Result of a test case run locally.
+#[derive(Debug, Serialize)]
+pub struct RunCase {
+    pub input: Vec<String>,
+    pub output: Option<String>,
+    pub stdout: String,
+}
+
+/// Result of a local run, `error` is set if the harness failed.
+#[derive(Debug, Serialize)]
+pub struct LocalRun {
+    pub cases: Vec<RunCase>,
+    pub error: Option<String>,
+}
+
+impl Harness {
+    pub fn new(
+        lang: &Lang,
+        code: &str,
+        signature: &Signature,
+        cases: &[Vec<Value>],
+    ) -> Result<Self> {
+        let harness = match lang {
+            Lang::Rust(_) => Harness {
+                filename: "main.rs",
+                source: rust_source(code, signature, cases)?,
+                build: Some(vec!["rustc", "--edition", "2021", "-o", "main", "main.rs"]),
+                run: vec!["./main"],
+            },
+            Lang::Python3(_) => Harness {
+                filename: "main.py",
+                source: python_source(code, signature, cases)?,
+                build: None,
+                run: vec!["python3", "main.py"],
+            },
+            Lang::Cpp(_) => Harness {
+                filename: "main.cpp",
+                source: cpp_source(code, signature, cases)?,
+                build: Some(vec!["g++", "-std=c++17", "-O2", "-o", "main", "main.cpp"]),
+                run: vec!["./main"],
+            },
+            _ => {
+                return Err(LeetUpError::Any(anyhow!(
+                    "Running {} solutions locally is not supported yet",
+                    lang.info().name
+                )))
+            }
+        };
+
+        Ok(harness)
+    }
+
+    /// Build and run the harness in `dir`, killing it after `timeout`.
+    pub fn run(
+        &self,
+        dir: &Path,
+        cases: &[Vec<Value>],
+        timeout: Option<Duration>,
+    ) -> Result<LocalRun> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(self.filename), &self.source)?;
+
+        if let Some(ref build) = self.build {
+            let output = execute(build, dir)?;
+            if !output.status.success() {
+                return Ok(LocalRun {
+                    cases: vec![],
+                    error: Some(format!(
+                        "Compile Error\n{}",
+                        String::from_utf8_lossy(&output.stderr)
+                    )),
+                });
+            }
+        }
+
+        let (output, timed_out) = execute_with_timeout(&self.run, dir, timeout)?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut results = vec![];
+        let mut case_stdout = String::new();
+        for line in stdout.lines() {
+            match line.strip_prefix(OUTPUT_MARKER) {
+                Some(result) => {
+                    results.push((Some(result.to_owned()), case_stdout.clone()));
+                    case_stdout.clear();
+                }
+                None => {
+                    case_stdout.push_str(line);
+                    case_stdout.push('\n');
+                }
+            }
+        }
+        // The case that crashed the harness has no output
+        if results.len() < cases.len() {
+            results.push((None, case_stdout));
+        }
+
+        let cases = cases
+            .iter()
+            .zip(results)
+            .map(|(input, (output, stdout))| RunCase {
+                input: input.iter().map(Value::to_string).collect(),
+                output,
+                stdout,
+            })
+            .collect();
+        let error = if timed_out {
+            Some(format!(
+                "Time Limit Exceeded\nKilled after {}s",
+                timeout.unwrap_or_default().as_secs()
+            ))
+        } else if output.status.success() {
+            None
+        } else {
+            Some(format!(
+                "Runtime Error\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        };
+
+        Ok(LocalRun { cases, error })
+    }
+}
+
+fn execute(command: &[&str], dir: &Path) -> Result<Output> {
+    let (program, args) = command.split_first().ok_or(LeetUpError::OptNone)?;
+    Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| spawn_error(program, e))
+}
+
+/// Like `execute`, but kill the process once `timeout` elapsed.
+///
+/// Returns whether the process was killed along with its output so far.
+fn execute_with_timeout(
+    command: &[&str],
+    dir: &Path,
+    timeout: Option<Duration>,
+) -> Result<(Output, bool)> {
+    let (program, args) = command.split_first().ok_or(LeetUpError::OptNone)?;
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| spawn_error(program, e))?;
+
+    // Drain the pipes while waiting so that a chatty solution does not block
+    let stdout = child.stdout.take().map(read_all);
+    let stderr = child.stderr.take().map(read_all);
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (status, false);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            let _ = child.kill();
+            break (child.wait()?, true);
+        }
+        thread::sleep(WAIT_INTERVAL);
+    };
+    let join = |reader: Option<JoinHandle<Vec<u8>>>| {
+        reader
+            .map(|reader| reader.join().unwrap_or_default())
+            .unwrap_or_default()
+    };
+
+    Ok((
+        Output {
+            status,
+            stdout: join(stdout),
+            stderr: join(stderr),
+        },
+        timed_out,
+    ))
+}
+
+fn read_all<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = vec![];
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn spawn_error(program: &str, e: io::Error) -> LeetUpError {
+    match e.kind() {
+        io::ErrorKind::NotFound => LeetUpError::Any(anyhow!(
+            "`{}` is required to run this solution locally",
+            program
+        )),
+        _ => e.into(),
+    }
+}
+
+fn rust_source(code: &str, signature: &Signature, cases: &[Vec<Value>]) -> Result<String> {
+    let mut source = String::from("#![allow(dead_code, unused_imports)]\n\n");
+    source.push_str(&helper_code("rust", HelperTypes::all()).unwrap_or_default());
+    source.push_str("\n\nstruct Solution;\n");
+    source.push_str(code);
+    source.push_str(
+        r#"
+trait Output {
+    fn output(&self) -> String;
+}
+
+macro_rules! impl_output {
+    ($($t:ty),*) => {
+        $(impl Output for $t {
+            fn output(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+impl_output!(i32, i64, bool);
+
+impl Output for f64 {
+    fn output(&self) -> String {
+        format!("{:.5}", self)
+    }
+}
+
+impl Output for String {
+    fn output(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl Output for char {
+    fn output(&self) -> String {
+        format!("\"{}\"", self)
+    }
+}
+
+impl<T: Output> Output for Vec<T> {
+    fn output(&self) -> String {
+        let values: Vec<String> = self.iter().map(Output::output).collect();
+        format!("[{}]", values.join(","))
+    }
+}
+
+impl Output for Option<Box<ListNode>> {
+    fn output(&self) -> String {
+        list_values(self).output()
+    }
+}
+
+impl Output for Option<std::rc::Rc<std::cell::RefCell<TreeNode>>> {
+    fn output(&self) -> String {
+        let values: Vec<String> = tree_values(self)
+            .iter()
+            .map(|v| v.map_or("null".to_owned(), |v| v.to_string()))
+            .collect();
+        format!("[{}]", values.join(","))
+    }
+}
+
+fn main() {
+"#,
+    );
+
+    let name = snake_case(&signature.name);
+    for case in cases {
+        source.push_str("    {\n");
+        let mut args = vec![];
+        for (i, ((_, value_type), value)) in signature.params.iter().zip(case).enumerate() {
+            let mutable = if i == 0 && signature.is_in_place() {
+                args.push(format!("&mut p{}", i));
+                "mut "
+            } else {
+                args.push(format!("p{}", i));
+                ""
+            };
+            source.push_str(&format!(
+                "        let {}p{}: {} = {};\n",
+                mutable,
+                i,
+                value_type.rust_type(),
+                value_type.rust_literal(value)?
+            ));
+        }
+        let call = format!("Solution::{}({})", name, args.join(", "));
+        if signature.is_in_place() {
+            source.push_str(&format!("        {};\n", call));
+            source.push_str(&format!(
+                "        println!(\"{}{{}}\", p0.output());\n",
+                OUTPUT_MARKER
+            ));
+        } else {
+            source.push_str(&format!(
+                "        println!(\"{}{{}}\", {}.output());\n",
+                OUTPUT_MARKER, call
+            ));
+        }
+        source.push_str("    }\n");
+    }
+    source.push_str("}\n");
+
+    Ok(source)
+}
+
+fn python_source(code: &str, signature: &Signature, cases: &[Vec<Value>]) -> Result<String> {
+    let mut source = String::from("import json\n");
+    source.push_str(&helper_code("python3", HelperTypes::all()).unwrap_or_default());
+    source.push_str("\n\n\n");
+    source.push_str(code);
+
+    let mut case_lines = String::new();
+    for case in cases {
+        let args = signature
+            .params
+            .iter()
+            .zip(case)
+            .map(|((_, value_type), value)| value_type.python_literal(value))
+            .collect::<Result<Vec<_>>>()?;
+        case_lines.push_str(&format!("        [{}],\n", args.join(", ")));
+    }
+    // A missing node is an empty list or tree rather than `null`
+    let output = if signature.is_in_place() {
+        signature.params[0].1.python_values("args[0]")
+    } else {
+        signature.return_type.python_values("result")
+    };
+    source.push_str(&format!(
+        r#"
+
+
+if __name__ == "__main__":
+    cases = [
+{cases}    ]
+    for args in cases:
+        result = Solution().{name}(*args)
+        print("{marker}" + json.dumps({output}, separators=(",", ":")), flush=True)
+"#,
+        cases = case_lines,
+        name = signature.name,
+        marker = OUTPUT_MARKER,
+        output = output
+    ));
+
+    Ok(source)
+}
+
+fn cpp_source(code: &str, signature: &Signature, cases: &[Vec<Value>]) -> Result<String> {
+    let mut source = helper_code("cpp", HelperTypes::all()).unwrap_or_default();
+    source.push_str("\n\n");
+    source.push_str(code);
+    source.push_str(
+        r#"
+
+string output(int value) { return to_string(value); }
+string output(long long value) { return to_string(value); }
+string output(bool value) { return value ? "true" : "false"; }
+string output(char value) { return "\"" + string(1, value) + "\""; }
+string output(const string& value) { return "\"" + value + "\""; }
+string output(double value) {
+    ostringstream out;
+    out << fixed << setprecision(5) << value;
+    return out.str();
+}
+string output(const optional<int>& value) { return value ? to_string(*value) : "null"; }
+
+template <typename T>
+string output(const vector<T>& values) {
+    string out = "[";
+    for (size_t i = 0; i < values.size(); i++) {
+        if (i > 0) out += ",";
+        out += output((T) values[i]);
+    }
+    return out + "]";
+}
+
+string output(ListNode* value) { return output(list_values(value)); }
+string output(TreeNode* value) { return output(tree_values(value)); }
+
+int main() {
+"#,
+    );
+
+    for case in cases {
+        source.push_str("    {\n");
+        let mut args = vec![];
+        for (i, ((_, value_type), value)) in signature.params.iter().zip(case).enumerate() {
+            args.push(format!("p{}", i));
+            source.push_str(&format!(
+                "        {} p{} = {};\n",
+                value_type.cpp_type(),
+                i,
+                value_type.cpp_literal(value)?
+            ));
+        }
+        let call = format!("Solution().{}({})", signature.name, args.join(", "));
+        if signature.is_in_place() {
+            source.push_str(&format!("        {};\n", call));
+            source.push_str(&format!(
+                "        cout << \"{}\" << output(p0) << endl;\n",
+                OUTPUT_MARKER
+            ));
+        } else {
+            source.push_str(&format!(
+                "        cout << \"{}\" << output({}) << endl;\n",
+                OUTPUT_MARKER, call
+            ));
+        }
+        source.push_str("    }\n");
+    }
+    source.push_str("}\n");
+
+    Ok(source)
+}
+
+#[test]
+fn test_value_type() {
+    let value_type = ValueType::from_str("list<integer[]>").unwrap();
+    assert_eq!(value_type.rust_type(), "Vec<Vec<i32>>");
+    assert_eq!(value_type.cpp_type(), "vector<vector<int>>");
+
+    let value = serde_json::json!([[1, 2], [3]]);
+    assert_eq!(
+        value_type.rust_literal(&value).unwrap(),
+        "vec![vec![1, 2], vec![3]]"
+    );
+    assert_eq!(value_type.cpp_literal(&value).unwrap(), "{{1, 2}, {3}}");
+
+    let value_type = ValueType::from_str("character[]").unwrap();
+    let value = serde_json::json!(["a", "'"]);
+    assert_eq!(
+        value_type.rust_literal(&value).unwrap(),
+        r#"vec!['a', '\'']"#
+    );
+    assert_eq!(value_type.cpp_literal(&value).unwrap(), r#"{'a', '\''}"#);
+
+    let value_type = ValueType::from_str("TreeNode").unwrap();
+    let value = serde_json::json!([1, null, 2]);
+    assert_eq!(
+        value_type.rust_literal(&value).unwrap(),
+        "to_tree(vec![Some(1), None, Some(2)])"
+    );
+    assert_eq!(
+        value_type.cpp_literal(&value).unwrap(),
+        "to_tree({1, nullopt, 2})"
+    );
+    assert_eq!(
+        value_type.python_literal(&value).unwrap(),
+        "to_tree([1, None, 2])"
+    );
+    assert_eq!(
+        ValueType::from_str("ListNode[]")
+            .unwrap()
+            .python_values("result"),
+        "[list_values(v) for v in result]"
+    );
+
+    assert!(ValueType::from_str("Node").is_err());
+    assert!(ValueType::String
+        .rust_literal(&serde_json::json!(1))
+        .is_err());
+}
+
+#[test]
+fn test_snake_case() {
+    assert_eq!(snake_case("twoSum"), "two_sum");
+    assert_eq!(snake_case("isValidBST"), "is_valid_bst");
+    assert_eq!(snake_case("getIPAddress"), "get_ip_address");
+    assert_eq!(snake_case("myAtoi"), "my_atoi");
+}
+
+#[test]
+fn test_signature_cases() {
+    let meta: MetaData = serde_json::from_value(serde_json::json!({
+        "name": "twoSum",
+        "params": [{"name": "nums", "type": "integer[]"}, {"name": "target", "type": "integer"}],
+        "return": {"type": "integer[]", "size": 2}
+    }))
+    .unwrap();
+    let signature = Signature::try_from(&meta).unwrap();
+    let cases = signature
+        .test_cases("[2,7,11,15]\n9\n\n[3,2,4]\n6\n")
+        .unwrap();
+    assert_eq!(cases.len(), 2);
+    assert_eq!(cases[1][0], serde_json::json!([3, 2, 4]));
+    assert!(signature.test_cases("[2,7,11,15]\n9\n[3,2,4]").is_err());
+
+    let lang = Lang::from_str("rust").unwrap();
+    let harness = Harness::new(&lang, "impl Solution {}", &signature, &cases).unwrap();
+    assert!(harness
+        .source
+        .contains("let p0: Vec<i32> = vec![3, 2, 4];\n        let p1: i32 = 6;"));
+    assert!(harness
+        .source
+        .contains("println!(\"@leetup=output {}\", Solution::two_sum(p0, p1).output());"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_execute_with_timeout() {
+    let dir = tempfile::tempdir().unwrap();
+    let (output, timed_out) = execute_with_timeout(
+        &["sh", "-c", "echo started; exec sleep 5"],
+        dir.path(),
+        Some(Duration::from_millis(200)),
+    )
+    .unwrap();
+    assert!(timed_out);
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "started\n");
+
+    let (output, timed_out) =
+        execute_with_timeout(&["sh", "-c", "echo done"], dir.path(), None).unwrap();
+    assert!(!timed_out);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+}
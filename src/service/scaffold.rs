@@ -0,0 +1,279 @@
+use html2text::from_read;
+use serde_json::Value;
+
+use crate::model::QuestionDetail;
+use crate::printer::decorator::normalize_html;
+use crate::service::{python_value, snake_case, Signature, ValueType};
+use crate::Result;
+
+/// Unit tests generated from the signature and examples of a problem.
+///
+/// `before_code` holds what the tests need to compile or import the solution.
+#[derive(Debug)]
+pub struct TestScaffold {
+    pub before_code: Option<String>,
+    pub after_code: String,
+}
+
+/// A test case of the examples with its expected output if known.
+#[derive(Debug)]
+struct Example {
+    input: Vec<Value>,
+    output: Option<Value>,
+}
+
+impl TestScaffold {
+    /// Generate unit tests in `lang`, `None` if the language or signature is not supported.
+    ///
+    /// Rust tests need `struct Solution;` which is only added if `has_solution_struct` is false.
+    pub fn new(
+        lang: &str,
+        question: &QuestionDetail,
+        signature: &Signature,
+        has_solution_struct: bool,
+    ) -> Result<Option<Self>> {
+        let examples = examples(question, signature)?;
+        if examples.is_empty() {
+            return Ok(None);
+        }
+
+        let scaffold = match lang {
+            "rust" => TestScaffold {
+                before_code: if has_solution_struct {
+                    None
+                } else {
+                    Some("struct Solution;".to_owned())
+                },
+                after_code: rust_tests(signature, &examples)?,
+            },
+            "python3" => TestScaffold {
+                before_code: Some("from typing import *\nimport unittest".to_owned()),
+                after_code: python_tests(signature, &examples)?,
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(scaffold))
+    }
+}
+
+/// Pair example test cases with the outputs stated in the problem's content.
+fn examples(question: &QuestionDetail, signature: &Signature) -> Result<Vec<Example>> {
+    let test_data = question
+        .example_testcases
+        .as_ref()
+        .or(question.sample_test_case.as_ref());
+    let cases = match test_data {
+        Some(test_data) => signature.test_cases(test_data)?,
+        None => return Ok(vec![]),
+    };
+
+    let outputs = question
+        .content
+        .as_deref()
+        .map(example_outputs)
+        .unwrap_or_default();
+    // Outputs can't be trusted to belong to a case unless all of them are found
+    let outputs = if outputs.len() == cases.len() {
+        outputs.into_iter().map(Some).collect()
+    } else {
+        vec![None; cases.len()]
+    };
+
+    Ok(cases
+        .into_iter()
+        .zip(outputs)
+        .map(|(input, output)| Example { input, output })
+        .collect())
+}
+
+/// Outputs of the examples in a problem's content, e.g. `Output: [0,1]`.
+fn example_outputs(content: &str) -> Vec<Value> {
+    let text = from_read(normalize_html(content).as_bytes(), usize::MAX);
+    text.lines()
+        .map(|line| line.replace("**", ""))
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("Output:")
+                .or_else(|| line.strip_prefix("输出："))
+                .map(|output| serde_json::from_str(output.trim()).unwrap_or(Value::Null))
+        })
+        .collect()
+}
+
+fn rust_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    match ident.as_str() {
+        "as" | "box" | "fn" | "impl" | "in" | "loop" | "match" | "mod" | "move" | "ref"
+        | "self" | "type" | "use" | "where" => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+fn rust_tests(signature: &Signature, examples: &[Example]) -> Result<String> {
+    let name = snake_case(&signature.name);
+    let in_place = signature.return_type == ValueType::Void;
+    let mut tests = String::from("#[cfg(test)]\nmod tests {\n    use super::*;\n");
+
+    for (i, example) in examples.iter().enumerate() {
+        tests.push_str(&format!(
+            "\n    #[test]\n    fn test_{}_{}() {{\n",
+            name,
+            i + 1
+        ));
+        let mut args = vec![];
+        for (j, ((param, value_type), value)) in
+            signature.params.iter().zip(&example.input).enumerate()
+        {
+            let ident = rust_ident(param);
+            let mutable = if j == 0 && in_place {
+                args.push(format!("&mut {}", ident));
+                "mut "
+            } else {
+                args.push(ident.to_owned());
+                ""
+            };
+            tests.push_str(&format!(
+                "        let {}{}: {} = {};\n",
+                mutable,
+                ident,
+                value_type.rust_type(),
+                value_type.rust_literal(value)?
+            ));
+        }
+
+        let call = format!("Solution::{}({})", name, args.join(", "));
+        let (value_type, actual) = if in_place {
+            tests.push_str(&format!("        {};\n", call));
+            (&signature.params[0].1, rust_ident(&signature.params[0].0))
+        } else {
+            (&signature.return_type, call)
+        };
+        let expected = example
+            .output
+            .as_ref()
+            .and_then(|output| value_type.rust_literal(output).ok());
+        match expected {
+            Some(expected) => {
+                tests.push_str(&format!("        assert_eq!({}, {});\n", actual, expected))
+            }
+            // Expected output is unknown, at least make sure the solution runs
+            None if in_place => {}
+            None => tests.push_str(&format!("        let _ = {};\n", actual)),
+        }
+        tests.push_str("    }\n");
+    }
+    tests.push('}');
+
+    Ok(tests)
+}
+
+fn python_tests(signature: &Signature, examples: &[Example]) -> Result<String> {
+    let name = snake_case(&signature.name);
+    let in_place = signature.return_type == ValueType::Void;
+    let mut tests = String::from("\n\nclass TestSolution(unittest.TestCase):");
+
+    for (i, example) in examples.iter().enumerate() {
+        tests.push_str(&format!("\n    def test_{}_{}(self):\n", name, i + 1));
+        let params: Vec<&str> = signature.params.iter().map(|(p, _)| p.as_str()).collect();
+        for ((param, value_type), value) in signature.params.iter().zip(&example.input) {
+            tests.push_str(&format!(
+                "        {} = {}\n",
+                param,
+                value_type.python_literal(value)?
+            ));
+        }
+
+        let call = format!("Solution().{}({})", signature.name, params.join(", "));
+        let (value_type, actual) = if in_place {
+            tests.push_str(&format!("        {}\n", call));
+            (&signature.params[0].1, params[0].to_owned())
+        } else {
+            (&signature.return_type, call)
+        };
+        match example.output {
+            Some(ref expected) => tests.push_str(&format!(
+                "        self.assertEqual({}, {})\n",
+                value_type.python_values(&actual),
+                python_value(expected)
+            )),
+            None if in_place => {}
+            None => tests.push_str(&format!("        {}\n", actual)),
+        }
+    }
+    tests.push_str("\n\nif __name__ == \"__main__\":\n    unittest.main()");
+
+    Ok(tests)
+}
+
+#[cfg(test)]
+fn two_sum() -> (QuestionDetail, Signature) {
+    use std::convert::TryFrom;
+
+    let question: QuestionDetail = serde_json::from_value(serde_json::json!({
+        "content": "<p><strong>Example 1:</strong></p>\n<pre>\n<strong>Input:</strong> nums = [2,7,11,15], target = 9\n<strong>Output:</strong> [0,1]\n<strong>Explanation:</strong> Because nums[0] + nums[1] == 9, we return [0, 1].\n</pre>\n<p><strong>Example 2:</strong></p>\n<pre>\n<strong>Input:</strong> nums = [3,2,4], target = 6\n<strong>Output:</strong> [1,2]\n</pre>",
+        "sampleTestCase": "[2,7,11,15]\n9",
+        "exampleTestcases": "[2,7,11,15]\n9\n[3,2,4]\n6",
+        "metaData": "{\"name\": \"twoSum\", \"params\": [{\"name\": \"nums\", \"type\": \"integer[]\"}, {\"name\": \"target\", \"type\": \"integer\"}], \"return\": {\"type\": \"integer[]\", \"size\": 2}}"
+    }))
+    .unwrap();
+    let signature = Signature::try_from(&question.meta_data().unwrap()).unwrap();
+
+    (question, signature)
+}
+
+#[test]
+fn test_rust_scaffold() {
+    let (question, signature) = two_sum();
+    let scaffold = TestScaffold::new("rust", &question, &signature, false)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(scaffold.before_code.as_deref(), Some("struct Solution;"));
+    assert!(scaffold.after_code.starts_with("#[cfg(test)]\nmod tests {"));
+    assert!(scaffold.after_code.contains(
+        "    #[test]
+    fn test_two_sum_2() {
+        let nums: Vec<i32> = vec![3, 2, 4];
+        let target: i32 = 6;
+        assert_eq!(Solution::two_sum(nums, target), vec![1, 2]);
+    }"
+    ));
+
+    let scaffold = TestScaffold::new("rust", &question, &signature, true)
+        .unwrap()
+        .unwrap();
+    assert!(scaffold.before_code.is_none());
+}
+
+#[test]
+fn test_python_scaffold() {
+    let (question, signature) = two_sum();
+    let scaffold = TestScaffold::new("python3", &question, &signature, false)
+        .unwrap()
+        .unwrap();
+
+    assert!(scaffold.after_code.contains(
+        "    def test_two_sum_1(self):
+        nums = [2, 7, 11, 15]
+        target = 9
+        self.assertEqual(Solution().twoSum(nums, target), [0, 1])"
+    ));
+    assert!(scaffold.after_code.ends_with("unittest.main()"));
+    assert!(TestScaffold::new("cpp", &question, &signature, false)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_example_outputs() {
+    let content = "<pre><strong>Input:</strong> s = \"abc\"\n<strong>Output:</strong> true\n</pre><pre>Input: s = \"\"\nOutput: \"x y\"</pre>";
+    assert_eq!(
+        example_outputs(content),
+        vec![Value::Bool(true), Value::String("x y".into())]
+    );
+    assert_eq!(
+        python_value(&serde_json::json!([true, null, "a"])),
+        "[True, None, \"a\"]"
+    );
+}
@@ -0,0 +1,159 @@
+use std::fs;
+
+use anyhow::anyhow;
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::{expand_home, LeetUpError, Result, SessionEncryption};
+
+/// Prefix of sealed values, followed by `salt:nonce:ciphertext` in base64.
+const SEALED_PREFIX: &str = "sealed:v1:";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const MIN_KEY_FILE_LEN: usize = 32;
+
+/// Secret the encryption key is derived from.
+enum Secret {
+    Passphrase(String),
+    KeyFile(Vec<u8>),
+}
+
+impl Secret {
+    fn read(encryption: &SessionEncryption) -> Result<Self> {
+        match encryption {
+            SessionEncryption::Passphrase => {
+                let passphrase = match std::env::var("LEETUP_PASSPHRASE") {
+                    Ok(passphrase) => passphrase,
+                    Err(_) => rpassword::prompt_password("Passphrase: ")?,
+                };
+                if passphrase.is_empty() {
+                    return Err(LeetUpError::Any(anyhow!("Passphrase can not be empty")));
+                }
+                Ok(Secret::Passphrase(passphrase))
+            }
+            SessionEncryption::KeyFile(path) => {
+                let path = expand_home(path);
+                let content = fs::read(&path).map_err(|e| {
+                    LeetUpError::Any(anyhow!("Unable to read key file {}: {}", path.display(), e))
+                })?;
+                if content.len() < MIN_KEY_FILE_LEN {
+                    return Err(LeetUpError::Any(anyhow!(
+                        "Key file {} is too short, expected at least {} bytes",
+                        path.display(),
+                        MIN_KEY_FILE_LEN
+                    )));
+                }
+                Ok(Secret::KeyFile(content))
+            }
+        }
+    }
+
+    /// Passphrases are stretched with Argon2id, key files are expected to be random already.
+    fn derive_key(&self, salt: &[u8]) -> Result<Key> {
+        let mut key = Key::default();
+        match self {
+            Secret::Passphrase(passphrase) => Argon2::default()
+                .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                .map_err(|e| LeetUpError::Any(anyhow!("Unable to derive key: {}", e)))?,
+            Secret::KeyFile(content) => key.copy_from_slice(
+                &Sha256::new()
+                    .chain_update(salt)
+                    .chain_update(content)
+                    .finalize(),
+            ),
+        }
+
+        Ok(key)
+    }
+}
+
+/// Whether `value` was sealed by `seal`.
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+/// Encrypt `plain` with a key derived from the secret of `encryption`.
+pub fn seal(plain: &str, encryption: &SessionEncryption) -> Result<String> {
+    seal_with(plain, &Secret::read(encryption)?)
+}
+
+/// Decrypt `value` if it was sealed, plain values are returned as is.
+pub fn unseal(value: &str, encryption: Option<&SessionEncryption>) -> Result<String> {
+    if !is_sealed(value) {
+        return Ok(value.to_owned());
+    }
+    let encryption = encryption.ok_or_else(|| {
+        LeetUpError::Any(anyhow!(
+            "Session is encrypted, set `session_encryption` in config.json to decrypt it or logout with `leetup user -l`"
+        ))
+    })?;
+
+    unseal_with(value, &Secret::read(encryption)?)
+}
+
+fn seal_with(plain: &str, secret: &Secret) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill(&mut salt);
+    rand::thread_rng().fill(&mut nonce);
+
+    let cipher = ChaCha20Poly1305::new(&secret.derive_key(&salt)?);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plain.as_bytes())
+        .map_err(|_| LeetUpError::Any(anyhow!("Unable to encrypt the session")))?;
+
+    Ok(format!(
+        "{}{}:{}:{}",
+        SEALED_PREFIX,
+        STANDARD.encode(salt),
+        STANDARD.encode(nonce),
+        STANDARD.encode(ciphertext)
+    ))
+}
+
+fn unseal_with(value: &str, secret: &Secret) -> Result<String> {
+    let malformed = || LeetUpError::Any(anyhow!("Encrypted session is malformed"));
+    let parts = value
+        .trim_start_matches(SEALED_PREFIX)
+        .split(':')
+        .map(|part| STANDARD.decode(part))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| malformed())?;
+    let (salt, nonce, ciphertext) = match parts.as_slice() {
+        [salt, nonce, ciphertext] if nonce.len() == NONCE_LEN => (salt, nonce, ciphertext),
+        _ => return Err(malformed()),
+    };
+
+    let cipher = ChaCha20Poly1305::new(&secret.derive_key(salt)?);
+    let plain = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext.as_slice())
+        .map_err(|_| {
+            LeetUpError::Any(anyhow!(
+                "Unable to decrypt the session, wrong passphrase or key file? Logout with `leetup user -l` to remove it"
+            ))
+        })?;
+
+    String::from_utf8(plain).map_err(|_| malformed())
+}
+
+#[test]
+fn test_seal_session() {
+    let session = r#"{"id":"session-id","csrf":"csrf-token"}"#;
+    let passphrase = Secret::Passphrase("correct horse battery staple".into());
+    let sealed = seal_with(session, &passphrase).unwrap();
+    assert!(is_sealed(&sealed));
+    assert!(!sealed.contains("session-id"));
+    assert_eq!(unseal_with(&sealed, &passphrase).unwrap(), session);
+    assert!(unseal_with(&sealed, &Secret::Passphrase("wrong".into())).is_err());
+
+    let key_file = Secret::KeyFile(vec![7; MIN_KEY_FILE_LEN]);
+    let sealed = seal_with(session, &key_file).unwrap();
+    assert_eq!(unseal_with(&sealed, &key_file).unwrap(), session);
+    assert_ne!(sealed, seal_with(session, &key_file).unwrap());
+
+    assert_eq!(unseal(session, None).unwrap(), session);
+    assert!(unseal(&sealed, None).is_err());
+}
@@ -1,8 +1,12 @@
 use std::str::FromStr;
+use std::sync::OnceLock;
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use cookie::Cookie;
 use serde::{Deserialize, Serialize};
 
+use crate::{service::unseal, Result, SessionEncryption};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Session {
     pub id: String,
@@ -13,6 +17,18 @@ impl Session {
     pub fn new(id: String, csrf: String) -> Self {
         Session { id, csrf }
     }
+
+    /// Expiry of the session as a UNIX timestamp, read from the JWT claims of
+    /// `LEETCODE_SESSION`. `None` if the session is not a JWT.
+    pub fn expires_at(&self) -> Option<u64> {
+        let payload = self.id.split('.').nth(1)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+        let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+
+        claims["expired_time_"]
+            .as_u64()
+            .or_else(|| claims["exp"].as_u64())
+    }
 }
 
 impl FromStr for Session {
@@ -47,6 +63,43 @@ fn session_to_cookie(id: &str, csrf: &str) -> String {
     s
 }
 
+/// Session cached by a previous login, decrypted on first use only.
+///
+/// Commands that never talk to the judge, e.g. logout or clearing the cache,
+/// work without the passphrase or key file of an encrypted session.
+#[derive(Debug, Default)]
+pub struct LazySession {
+    cached: Option<String>,
+    encryption: Option<SessionEncryption>,
+    session: OnceLock<Option<Session>>,
+}
+
+impl LazySession {
+    pub fn new(cached: Option<String>, encryption: Option<SessionEncryption>) -> Self {
+        LazySession {
+            cached,
+            encryption,
+            session: OnceLock::new(),
+        }
+    }
+
+    /// The session, `None` if the user is not logged in.
+    pub fn get(&self) -> Result<Option<&Session>> {
+        if let Some(session) = self.session.get() {
+            return Ok(session.as_ref());
+        }
+        let session = match self.cached {
+            Some(ref val) => {
+                let val = unseal(val, self.encryption.as_ref())?;
+                Some(serde_json::from_str::<Session>(&val)?)
+            }
+            None => None,
+        };
+
+        Ok(self.session.get_or_init(|| session).as_ref())
+    }
+}
+
 impl From<Session> for String {
     fn from(session: Session) -> Self {
         session_to_cookie(&session.id, &session.csrf)
@@ -67,3 +120,32 @@ fn test_cookie_parser() {
     assert!(!session.csrf.is_empty());
     assert!(!session.id.is_empty());
 }
+
+#[test]
+fn test_session_expiry() {
+    let claims = URL_SAFE_NO_PAD.encode(r#"{"_auth_user_id":"1","expired_time_":1798761599}"#);
+    let session = Session::new(
+        format!("eyJ0eXAiOiJKV1QifQ.{}.signature", claims),
+        "csrf".into(),
+    );
+    assert_eq!(session.expires_at(), Some(1_798_761_599));
+
+    let session = Session::new("session-id".into(), "csrf".into());
+    assert_eq!(session.expires_at(), None);
+}
+
+#[test]
+fn test_lazy_session() {
+    let session = LazySession::new(None, None);
+    assert!(session.get().unwrap().is_none());
+
+    let session = LazySession::new(
+        Some(r#"{"id":"session-id","csrf":"csrf-token"}"#.into()),
+        None,
+    );
+    assert_eq!(session.get().unwrap().unwrap().id, "session-id");
+
+    // Decrypted on first use only, construction never fails
+    let session = LazySession::new(Some("sealed:v1:a:b:c".into()), None);
+    assert!(session.get().is_err());
+}
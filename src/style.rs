@@ -0,0 +1,84 @@
+use std::ffi::OsString;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ansi_term::Style;
+use anyhow::anyhow;
+
+use crate::LeetUpError;
+
+/// Whether colors and unicode icons are used, set once by `init`.
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// When to style the output, from `--color`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Style unless stdout is not a terminal or `NO_COLOR` is set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = LeetUpError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(LeetUpError::Any(anyhow!(
+                "Color choice not supported, expected auto, always or never"
+            ))),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// Resolve the choice, `--color` takes precedence over `NO_COLOR` as per https://no-color.org.
+    fn is_enabled(&self, no_color: Option<OsString>, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => no_color.is_none_or(|v| v.is_empty()) && is_terminal,
+        }
+    }
+}
+
+/// Enable or disable styling of the output for the rest of the process.
+pub fn init(choice: ColorChoice) {
+    let enabled = choice.is_enabled(
+        std::env::var_os("NO_COLOR"),
+        std::io::stdout().is_terminal(),
+    );
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether colors and unicode icons can be printed.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Paint `s` with `style`, e.g. `Red` or `Red.bold()`, unless styling is disabled.
+pub fn paint<S: Into<Style>>(style: S, s: &str) -> String {
+    if is_enabled() {
+        style.into().paint(s).to_string()
+    } else {
+        s.to_owned()
+    }
+}
+
+#[test]
+fn test_color_choice() {
+    let no_color = || Some(OsString::from("1"));
+
+    assert!(ColorChoice::Auto.is_enabled(None, true));
+    assert!(ColorChoice::Auto.is_enabled(Some(OsString::new()), true));
+    assert!(!ColorChoice::Auto.is_enabled(None, false));
+    assert!(!ColorChoice::Auto.is_enabled(no_color(), true));
+    assert!(ColorChoice::Always.is_enabled(no_color(), false));
+    assert!(!ColorChoice::Never.is_enabled(None, true));
+    assert!(ColorChoice::from_str("sometimes").is_err());
+}
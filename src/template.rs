@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Copy, Clone)]
 pub enum Pattern {
     LeetUpInfo,
@@ -16,6 +18,8 @@ pub enum InjectPosition {
     BeforeCodeExclude,
     AfterCode,
     BeforeFunctionDefinition,
+    // Unit tests generated from the problem's examples
+    Tests,
 }
 
 impl From<Pattern> for String {
@@ -31,6 +35,7 @@ impl From<Pattern> for String {
                 InjectPosition::BeforeFunctionDefinition => {
                     "@leetup=inject:before_function_definition".into()
                 }
+                InjectPosition::Tests => "@leetup=inject:tests".into(),
             },
             Pattern::Problem => "@leetup=problem".into(),
             Pattern::WorkingDir => "@leetup=working_dir".into(),
@@ -38,15 +43,15 @@ impl From<Pattern> for String {
     }
 }
 
-impl<'a> From<&'a Pattern> for String {
+impl From<&Pattern> for String {
     fn from(p: &Pattern) -> Self {
         String::from(*p)
     }
 }
 
-impl ToString for Pattern {
-    fn to_string(&self) -> String {
-        String::from(*self)
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", String::from(*self))
     }
 }
 
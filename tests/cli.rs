@@ -1,13 +1,16 @@
 use std::process::Command;
 
 use assert_cmd::prelude::*;
+use predicates::prelude::*;
 use predicates::str::contains;
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use std::fs::File;
-    use std::io::Read;
+    use serde_json::json;
 
+    use super::common::{read_file, strip_ansi, Env};
     use super::*;
 
     #[test]
@@ -19,57 +22,1172 @@ mod tests {
             .stdout(contains(env!("CARGO_PKG_VERSION")));
     }
 
-    fn _get_id(problem: &str) -> usize {
-        println!("{}", problem);
+    fn get_id(problem: &str) -> usize {
         let start_index = problem.find(" [").unwrap();
         let end_index = problem.find(']').unwrap();
         let id = problem.get(start_index + 2..end_index).unwrap().trim();
-        println!("{}", id);
         id.parse().unwrap()
     }
 
-    fn _list_problems() {
-        let bytes: Vec<u8> = Command::cargo_bin("leetup")
-            .unwrap()
+    fn pick_two_sum(env: &Env) -> String {
+        let bytes = env
+            .leetup()
+            .args(["pick", "-l", "rust", "1"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        strip_ansi(&bytes)
+            .replace("Generated: ", "")
+            .trim_end()
+            .to_owned()
+    }
+
+    #[test]
+    fn list_problems() {
+        let env = Env::logged_in();
+        let bytes = env
+            .leetup()
             .args(["list", "-oi"])
             .assert()
+            .success()
             .get_output()
             .stdout
             .clone();
-        let result: Vec<String> = String::from_utf8(bytes)
+        let output = strip_ansi(&bytes);
+        let result: Vec<&str> = output.lines().collect();
+
+        // Test OrderBy works by check first and last id
+        assert_eq!(4, result.len());
+        assert_eq!(1, get_id(result[0]));
+        assert_eq!(226, get_id(result[3]));
+        assert!(result[0].contains("Two Sum"));
+        assert_eq!(1, env.judge.requests("/api/problems/all").len());
+    }
+
+    #[test]
+    fn list_problems_json() {
+        let env = Env::logged_in();
+        let output = env
+            .leetup()
+            .args(["list", "-q", "d", "--output", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let problems: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+        assert_eq!(
+            problems,
+            json!([{
+                "id": 1,
+                "title": "Two Sum",
+                "slug": "two-sum",
+                "difficulty": "Easy",
+                "paid_only": false,
+                "favorite": false,
+                "status": "ac"
+            }])
+        );
+
+        // Attempted but not accepted problems are not done
+        let output = env
+            .leetup()
+            .args(["list", "-q", "eD", "--output", "json"])
+            .output()
+            .unwrap();
+        let problems: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(problems[0]["slug"], "invert-binary-tree");
+        assert_eq!(problems.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn list_problems_plain() {
+        let env = Env::logged_in();
+        let output = env
+            .leetup()
+            .args(["--output", "plain", "list", "-s"])
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("Two Sum"));
+        assert!(stdout.contains("Progress:"));
+        assert!(!stdout.contains('\x1b'));
+    }
+
+    #[test]
+    fn list_problems_color() {
+        let env = Env::logged_in();
+        let list = |args: &[&str], no_color: bool| {
+            let mut cmd = env.leetup();
+            if no_color {
+                cmd.env("NO_COLOR", "1");
+            }
+            let output = cmd.args(args).output().unwrap();
+            String::from_utf8(output.stdout).unwrap()
+        };
+
+        // Output is not a terminal
+        let stdout = list(&["list", "-q", "d"], false);
+        assert!(!stdout.contains('\x1b'));
+        assert!(stdout.starts_with("     v [ 1  ] Two Sum"));
+
+        let stdout = list(&["list", "-q", "d", "--color", "always"], false);
+        assert!(stdout.contains("\x1b[32m✔\x1b[0m"));
+        // `--color` takes precedence over NO_COLOR
+        assert!(list(&["list", "--color", "always"], true).contains('\x1b'));
+        assert!(!list(&["list", "--color", "auto"], true).contains('\x1b'));
+    }
+
+    #[test]
+    fn list_problems_from_cache() {
+        let env = Env::logged_in();
+        env.leetup().args(["list"]).assert().success();
+        env.leetup()
+            .args(["list", "median"])
+            .assert()
+            .success()
+            .stdout(contains("Median of Two Sorted Arrays"));
+
+        assert_eq!(1, env.judge.requests("/api/problems/all").len());
+    }
+
+    fn write_cookies(env: &Env, session: &str, expires: u64) -> String {
+        let path = env.path("cookies.txt");
+        let cookies = format!(
+            "# Netscape HTTP Cookie File\n\
+             127.0.0.1\tFALSE\t/\tFALSE\t0\tcsrftoken\tcsrf-token\n\
+             #HttpOnly_127.0.0.1\tFALSE\t/\tFALSE\t{}\tLEETCODE_SESSION\t{}\n",
+            expires, session
+        );
+        std::fs::write(&path, cookies).unwrap();
+        path.to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn login_import_cookies() {
+        let env = Env::new();
+        let cookies = write_cookies(&env, "session-id", 0);
+        env.leetup()
+            .args(["user", "--import-cookies", &cookies])
+            .assert()
+            .success()
+            .stdout(contains("User logged in as leetup!"));
+
+        let generated = pick_two_sum(&env);
+        env.leetup()
+            .args(["test", &generated, "-t", "[2,7,11,15]\n9"])
+            .assert()
+            .success();
+        let requests = env.judge.requests("/problems/two-sum/interpret_solution/");
+        assert!(requests[0].headers["cookie"].contains("LEETCODE_SESSION=session-id"));
+        assert_eq!("csrf-token", requests[0].headers["x-csrftoken"]);
+    }
+
+    #[test]
+    fn login_import_cookies_invalid() {
+        let env = Env::new();
+        let cookies = write_cookies(&env, "session-id", 946684800);
+        env.leetup()
+            .args(["user", "--import-cookies", &cookies])
+            .assert()
+            .failure()
+            .stderr(contains("No LEETCODE_SESSION and csrftoken cookies"));
+
+        let cookies = write_cookies(&env, "revoked", 0);
+        env.leetup()
+            .args(["user", "--import-cookies", &cookies])
+            .assert()
+            .failure()
+            .stderr(contains("Session is expired or invalid"));
+
+        // The session is not cached
+        let generated = pick_two_sum(&env);
+        env.leetup()
+            .args(["test", &generated, "-t", "[2,7,11,15]\n9"])
+            .assert()
+            .success();
+        let requests = env.judge.requests("/problems/two-sum/interpret_solution/");
+        assert!(!requests[0].headers.contains_key("cookie"));
+    }
+
+    #[test]
+    fn user_status() {
+        let env = Env::new();
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Not logged in"));
+
+        let env = env.login();
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Logged in as leetup").and(contains("Premium: No")));
+
+        let output = env
+            .leetup()
+            .args(["user", "--status", "--output", "json"])
+            .output()
+            .unwrap();
+        let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(
+            status,
+            json!({"signed_in": true, "username": "leetup", "premium": false, "expires_at": null})
+        );
+    }
+
+    #[test]
+    fn expired_session() {
+        let env = Env::new();
+        let generated = pick_two_sum(&env);
+        let env = env.login_with("expired");
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Session is expired or invalid"));
+
+        env.leetup()
+            .args(["list"])
+            .assert()
+            .failure()
+            .stderr(contains("Session is expired or invalid (403 Forbidden)"));
+
+        env.leetup()
+            .args(["test", &generated, "-t", "[2,7,11,15]\n9"])
+            .assert()
+            .failure()
+            .stderr(
+                contains("Session is expired or invalid")
+                    .and(contains("login again using `leetup user -c`")),
+            );
+    }
+
+    #[test]
+    fn profiles() {
+        let env = Env::with_config(json!({"profiles": {"work": {"lang": "python3"}}})).login();
+        env.leetup()
+            .args(["--profile", "work", "user", "-c"])
+            .write_stdin("csrf-work\nsession-id\n")
+            .assert()
+            .success();
+
+        env.leetup()
+            .args(["user", "--status", "--profile", "work"])
+            .assert()
+            .success()
+            .stdout(contains("Logged in as leetup"));
+        env.leetup()
+            .args(["--profile", "personal", "user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Not logged in"));
+        env.leetup()
+            .args(["--profile", "../work", "list"])
+            .assert()
+            .failure()
+            .stderr(contains("Invalid profile"));
+
+        // `lang` of the profile is used by default
+        env.leetup()
+            .args(["--profile", "work", "pick", "1"])
+            .assert()
+            .success();
+        let generated = env.path("two-sum.py");
+        assert!(generated.exists());
+
+        env.leetup()
+            .args(["--profile", "work", "test", generated.to_str().unwrap()])
+            .args(["-t", "[2,7,11,15]\n9"])
+            .assert()
+            .success();
+        let requests = env.judge.requests("/problems/two-sum/interpret_solution/");
+        assert_eq!("csrf-work", requests[0].headers["x-csrftoken"]);
+
+        // Problems cached by `pick` of `work` are not shared with the default profile
+        env.leetup()
+            .args(["list", "--profile", "work"])
+            .assert()
+            .success();
+        assert_eq!(1, env.judge.requests("/api/problems/all").len());
+        env.leetup().args(["list"]).assert().success();
+        assert_eq!(2, env.judge.requests("/api/problems/all").len());
+    }
+
+    /// Content of the cache logs in leetup home.
+    fn read_cache_logs(env: &Env) -> String {
+        std::fs::read_dir(env.home.path())
             .unwrap()
-            .split('\n')
-            .map(String::from)
+            .flatten()
+            .filter(|entry| entry.path().extension() == Some("log".as_ref()))
+            .map(|entry| read_file(entry.path()))
+            .collect()
+    }
+
+    #[test]
+    fn logout_scrubs_session() {
+        let env = Env::logged_in();
+        assert!(read_cache_logs(&env).contains("session-id"));
+
+        env.leetup()
+            .args(["user", "-l"])
+            .assert()
+            .success()
+            .stdout(contains("User logged out!"));
+        assert!(!read_cache_logs(&env).contains("session-id"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = |path: &std::path::Path| {
+                std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+            };
+            assert_eq!(0o700, mode(env.home.path()));
+            for entry in std::fs::read_dir(env.home.path()).unwrap().flatten() {
+                if entry.path().extension() == Some("log".as_ref()) {
+                    assert_eq!(0o600, mode(&entry.path()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn session_encryption_key_file() {
+        let key_dir = tempfile::tempdir().unwrap();
+        let key_file = key_dir.path().join("leetup.key");
+        std::fs::write(&key_file, [42u8; 32]).unwrap();
+        let env = Env::with_config(json!({"session_encryption": {"key_file": key_file}})).login();
+        assert!(!read_cache_logs(&env).contains("session-id"));
+
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Logged in as leetup"));
+
+        env.leetup().args(["list"]).assert().success();
+
+        std::fs::write(&key_file, [7u8; 32]).unwrap();
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .failure()
+            .stderr(contains("Unable to decrypt the session"));
+
+        // Commands not talking to the judge never decrypt the session
+        env.leetup()
+            .args(["list"])
+            .assert()
+            .success()
+            .stdout(contains("Two Sum"));
+        env.leetup()
+            .args(["user", "--logout"])
+            .assert()
+            .success()
+            .stdout(contains("User logged out!"));
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Not logged in"));
+    }
+
+    #[test]
+    fn session_encryption_invalid_config() {
+        let key_dir = tempfile::tempdir().unwrap();
+        let key_file = key_dir.path().join("leetup.key");
+        std::fs::write(&key_file, [42u8; 32]).unwrap();
+        let login = |env: &Env, session: &str| {
+            env.leetup()
+                .args(["user", "-c"])
+                .write_stdin(format!("csrf-token\n{}\n", session))
+                .assert()
+        };
+
+        // A broken section is ignored, the session is still encrypted
+        let env = Env::with_config(json!({
+            "session_encryption": {"key_file": key_file},
+            "judge": {"timout": 5}
+        }));
+        login(&env, "secret-session-value").success();
+        assert!(!read_cache_logs(&env).contains("secret-session-value"));
+
+        // An unparsable config fails instead of falling back to defaults
+        env.set_config(json!({"session_encryption": {"keyfile": key_file}}));
+        login(&env, "other-session-value")
+            .failure()
+            .stderr(contains("Invalid config"));
+        assert!(!read_cache_logs(&env).contains("other-session-value"));
+
+        // An encrypted session is never replaced with a plain one
+        env.set_config(json!({}));
+        login(&env, "other-session-value")
+            .failure()
+            .stderr(contains("Cached session is encrypted"));
+        assert!(!read_cache_logs(&env).contains("other-session-value"));
+    }
+
+    #[test]
+    fn session_encryption_passphrase() {
+        let env = Env::with_config(json!({"session_encryption": "passphrase"}));
+        let leetup = |passphrase: &str| {
+            let mut cmd = env.leetup();
+            cmd.env("LEETUP_PASSPHRASE", passphrase);
+            cmd
+        };
+        leetup("secret")
+            .args(["user", "-c"])
+            .write_stdin("csrf-token\nsession-id\n")
+            .assert()
+            .success();
+        assert!(!read_cache_logs(&env).contains("session-id"));
+
+        leetup("secret")
+            .args(["user", "--status"])
+            .assert()
+            .success()
+            .stdout(contains("Logged in as leetup"));
+        leetup("guess")
+            .args(["user", "--status"])
+            .assert()
+            .failure()
+            .stderr(contains("Unable to decrypt the session"));
+
+        // No passphrase is needed without talking to the judge
+        env.leetup().args(["cache", "clear"]).assert().success();
+        env.leetup().args(["user", "--logout"]).assert().success();
+    }
+
+    #[test]
+    fn cache_stats_and_clear() {
+        let env = Env::logged_in();
+        env.leetup().args(["list"]).assert().success();
+        env.leetup()
+            .args(["cache", "stats"])
+            .assert()
+            .success()
+            .stdout(contains("problems").and(contains("session")));
+
+        let output = env
+            .leetup()
+            .args(["cache", "stats", "--output", "json"])
+            .output()
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let problems = &stats["entries"].as_array().unwrap()[0];
+        assert_eq!("problems", problems["key"]);
+        assert!(problems["expires_at"].is_u64());
+
+        env.leetup()
+            .args(["cache", "clear", "problems"])
+            .assert()
+            .success()
+            .stdout(contains("Removed 1 entries"));
+        env.leetup()
+            .args(["cache", "clear", "missing"])
+            .assert()
+            .failure()
+            .stderr(contains("Key `missing` not found"));
+        env.leetup().args(["list"]).assert().success();
+        assert_eq!(2, env.judge.requests("/api/problems/all").len());
+
+        // The session is kept unless cleared explicitly
+        env.leetup().args(["cache", "clear"]).assert().success();
+        env.leetup()
+            .args(["user", "--status"])
+            .assert()
+            .stdout(contains("Logged in as leetup"));
+        env.leetup()
+            .args(["cache", "compact"])
+            .assert()
+            .success()
+            .stdout(contains("Compacted cache from"));
+    }
+
+    #[test]
+    fn daily_problem_cached_once() {
+        let env = Env::new();
+        for _ in 0..2 {
+            env.leetup()
+                .args(["daily"])
+                .assert()
+                .success()
+                .stdout(contains("Two Sum"));
+        }
+        let daily_requests = env
+            .judge
+            .requests("/graphql")
+            .iter()
+            .filter(|request| request.json()["operationName"] == "questionOfToday")
+            .count();
+        assert_eq!(1, daily_requests);
+
+        // The daily challenge is kept in a single entry, overwritten every day
+        let output = env
+            .leetup()
+            .args(["cache", "stats", "--output", "json"])
+            .output()
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let keys: Vec<&str> = stats["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|entry| entry["key"].as_str())
+            .filter(|key| key.starts_with("daily"))
             .collect();
+        assert_eq!(vec!["daily"], keys);
+    }
 
-        let n = result.len() - 1;
+    #[test]
+    fn pick_random_unsolved() {
+        let env = Env::new().login();
+        // Invert Binary Tree was attempted but not accepted yet
+        env.leetup()
+            .args(["pick", "-r", "-q", "e", "-l", "rust"])
+            .assert()
+            .success();
+        assert!(env.path("invert-binary-tree.rs").exists());
+        // No locked problem is a candidate, premium status is not needed
+        let premium_requests = |env: &Env| {
+            env.judge
+                .requests("/graphql")
+                .iter()
+                .filter(|request| request.json()["operationName"] == "globalData")
+                .count()
+        };
+        assert_eq!(0, premium_requests(&env));
 
-        // Test OrderBy works by check first and last id
-        //
-        // NOTE: For some reason, result.last() is empty!
-        assert_eq!(1, _get_id(result.get(0).as_ref().unwrap()));
-        assert_eq!(n, _get_id(result.get(n - 1).as_ref().unwrap()));
+        // Locked problems are skipped for free users, the status is cached
+        for _ in 0..2 {
+            env.leetup()
+                .args(["pick", "-r", "-q", "m", "-l", "rust"])
+                .assert()
+                .failure();
+        }
+        assert_eq!(1, premium_requests(&env));
+    }
+
+    #[test]
+    fn cache_refresh_problems() {
+        let env = Env::with_config(json!({"cache": {"problems_ttl": 1}})).login();
+        env.leetup().args(["list"]).assert().success();
+        env.leetup()
+            .args(["cache", "refresh"])
+            .assert()
+            .success()
+            .stdout(contains("Refreshed 4 problems"));
+        assert_eq!(2, env.judge.requests("/api/problems/all").len());
+
+        // Stale problems are fetched again
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        env.leetup().args(["list"]).assert().success();
+        assert_eq!(3, env.judge.requests("/api/problems/all").len());
+    }
+
+    fn question_detail_requests(env: &Env) -> usize {
+        env.judge
+            .requests("/graphql")
+            .iter()
+            .filter(|request| request.json()["operationName"] == "getQuestionDetail")
+            .count()
+    }
+
+    #[test]
+    fn problem_detail_from_cache() {
+        let env = Env::new();
+        pick_two_sum(&env);
+        env.leetup()
+            .args(["show", "two-sum"])
+            .assert()
+            .success()
+            .stdout(contains("Hint 1:"));
+        assert_eq!(1, question_detail_requests(&env));
+
+        env.leetup()
+            .args(["show", "two-sum", "--refresh"])
+            .assert()
+            .success();
+        assert_eq!(2, question_detail_requests(&env));
+
+        // The judge is not reachable for question details anymore
+        let config = json!({
+            "lang": "rust",
+            "urls": {
+                "base": env.judge.base,
+                "graphql": "http://127.0.0.1:1/graphql"
+            }
+        });
+        std::fs::write(env.home.path().join("config.json"), config.to_string()).unwrap();
+        std::fs::remove_file(env.path("two-sum.rs")).unwrap();
+        pick_two_sum(&env);
+        env.leetup()
+            .args(["show", "1"])
+            .assert()
+            .success()
+            .stdout(contains("Two Sum"));
+        env.leetup()
+            .args(["show", "two-sum", "--refresh"])
+            .assert()
+            .failure();
+    }
+
+    #[test]
+    fn sync_problems_offline() {
+        let env = Env::logged_in();
+        env.leetup().args(["show", "two-sum"]).assert().success();
+        env.leetup()
+            .args(["sync", "-j", "2"])
+            .assert()
+            .success()
+            .stdout(contains("Synced 1 problems, 1 already cached"))
+            .stdout(contains("1 problems failed"))
+            .stderr(contains("Failed to sync median-of-two-sorted-arrays"));
+        // The paid only problem is skipped
+        assert_eq!(3, question_detail_requests(&env));
+
+        // Only the failed problem is fetched again
+        env.leetup()
+            .args(["sync"])
+            .assert()
+            .success()
+            .stdout(contains("Synced 0 problems, 2 already cached"));
+        assert_eq!(4, question_detail_requests(&env));
+
+        let requests = env.judge.request_count();
+        env.leetup()
+            .args(["list", "--offline"])
+            .assert()
+            .success()
+            .stdout(contains("Invert Binary Tree"));
+        env.leetup()
+            .args(["show", "invert-binary-tree", "--offline"])
+            .assert()
+            .success()
+            .stdout(contains("Invert Binary Tree"));
+        env.leetup()
+            .args(["pick", "1", "-l", "rust", "--offline"])
+            .assert()
+            .success();
+        assert!(env.path("two-sum.rs").is_file());
+        env.leetup()
+            .args(["show", "median-of-two-sorted-arrays", "--offline"])
+            .assert()
+            .failure()
+            .stderr(contains(
+                "Problem `median-of-two-sorted-arrays` is not cached, run `leetup sync`",
+            ));
+        env.leetup()
+            .args(["submit", "two-sum.rs", "--offline"])
+            .assert()
+            .failure()
+            .stderr(contains("in offline mode"));
+        assert_eq!(requests, env.judge.request_count());
+    }
+
+    #[test]
+    fn offline_without_cache() {
+        let env = Env::logged_in();
+        env.leetup()
+            .args(["show", "two-sum", "--offline"])
+            .assert()
+            .failure()
+            .stderr(contains("List of problems is not cached"));
+        assert!(env.judge.requests("/api/problems/all").is_empty());
     }
 
-    #[ignore = "Not passing in CI -- works locally"]
-    #[allow(dead_code)]
+    #[test]
     fn pick_problem_lang_rust() {
-        let bytes: Vec<u8> = Command::cargo_bin("leetup")
+        let env = Env::new();
+        let generated = pick_two_sum(&env);
+
+        assert_eq!(env.path("two-sum.rs").to_str().unwrap(), generated);
+        let buffer = read_file(generated);
+        assert!(buffer.contains("// @leetup=custom\n// @leetup=info id=1 lang=rust slug=two-sum"));
+        assert!(buffer.contains("// @leetup=code\n"));
+        assert!(buffer.contains("pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32>"));
+    }
+
+    #[test]
+    fn show_problem() {
+        let env = Env::new();
+        let bytes = env
+            .leetup()
+            .args(["show", "two-sum"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let output = strip_ansi(&bytes);
+
+        assert!(output.contains("[1] Two Sum  Easy"));
+        assert!(output.contains("2 <= nums.length <= 10^4"));
+    }
+
+    #[test]
+    fn test_problem() {
+        let env = Env::logged_in();
+        let generated = pick_two_sum(&env);
+
+        let bytes = env
+            .leetup()
+            .args(["test", &generated, "-t", "[2,7,11,15]\n9"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let output = strip_ansi(&bytes);
+        assert!(output.contains("Accepted"));
+
+        let requests = env.judge.requests("/problems/two-sum/interpret_solution/");
+        assert_eq!(1, requests.len());
+        let request = &requests[0];
+        let body = request.json();
+        assert_eq!("rust", body["lang"]);
+        assert_eq!("[2,7,11,15]\n9", body["data_input"]);
+        assert!(request.headers["cookie"].contains("LEETCODE_SESSION=session-id"));
+        assert_eq!("csrf-token", request.headers["x-csrftoken"]);
+
+        // The judge is polled until the result is ready
+        let check = "/submissions/detail/runcode_1694281112.034828_DfKA6OnxO1/check/";
+        assert_eq!(3, env.judge.requests(check).len());
+    }
+
+    #[test]
+    fn submit_problem() {
+        let env = Env::logged_in();
+        let generated = pick_two_sum(&env);
+
+        let bytes = env
+            .leetup()
+            .args(["submit", &generated])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let output = strip_ansi(&bytes);
+        assert!(output.contains("Accepted"));
+        assert!(output.contains("63/63"));
+
+        let requests = env.judge.requests("/problems/two-sum/submit/");
+        assert_eq!(1, requests.len());
+        let body = requests[0].json();
+        let typed_code = body["typed_code"].as_str().unwrap();
+        assert!(typed_code.contains("pub fn two_sum"));
+        assert!(!typed_code.contains("@leetup"));
+        assert_eq!(
+            3,
+            env.judge
+                .requests("/submissions/detail/1044907055/check/")
+                .len()
+        );
+    }
+
+    #[test]
+    fn submit_problem_json() {
+        let env = Env::logged_in();
+        let generated = pick_two_sum(&env);
+
+        let output = env
+            .leetup()
+            .args(["submit", &generated, "--output", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        // Only the result is printed, without the spinner
+        let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(result["verdict"], "accepted");
+        assert_eq!(result["status_msg"], "Accepted");
+        assert_eq!(result["total_correct"], 63);
+    }
+
+    #[test]
+    fn test_problem_json() {
+        let env = Env::logged_in();
+        let generated = pick_two_sum(&env);
+
+        let output = env
+            .leetup()
+            .args(["--output", "json", "test", &generated, "-t", "[1,2]\n3"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert!(result["verdict"].is_string());
+        assert_eq!(result["test_data"], json!(["[1,2]", "3"]));
+        assert!(result["code_answer"].is_array());
+    }
+
+    #[test]
+    fn messages_json() {
+        let env = Env::new();
+        let json_output = |args: &[&str], stdin: &str| -> serde_json::Value {
+            let output = env
+                .leetup()
+                .args(args)
+                .args(["--output", "json"])
+                .write_stdin(stdin)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            serde_json::from_slice(&output.stdout).unwrap()
+        };
+
+        // Prompts go to stderr, stdout only has the result
+        let result = json_output(&["user", "-c"], "csrf-token\nsession-id\n");
+        assert_eq!(result["logged_in"], true);
+        let result = json_output(&["pick", "-l", "rust", "1"], "");
+        assert!(result["generated"]
+            .as_str()
             .unwrap()
-            .args(["pick", "-l", "rust", "1"])
+            .ends_with("two-sum.rs"));
+        let result = json_output(&["cache", "refresh"], "");
+        assert_eq!(result["refreshed"], 4);
+        let result = json_output(&["cache", "compact"], "");
+        assert!(result["compacted_size"].is_u64());
+        let result = json_output(&["cache", "clear"], "");
+        assert!(result["removed"].as_u64().unwrap() > 0);
+        let result = json_output(&["sync"], "");
+        assert_eq!(result["synced"], 2);
+        assert_eq!(result["cached"], 0);
+        assert_eq!(result["failed"].as_array().unwrap().len(), 1);
+        let result = json_output(&["user", "--logout"], "");
+        assert_eq!(result["logged_out"], true);
+    }
+
+    #[test]
+    fn test_problem_expired_session_json() {
+        let env = Env::new();
+        let generated = pick_two_sum(&env);
+        let env = env.login_with("expired");
+
+        let output = env
+            .leetup()
+            .args(["--output", "json", "test", &generated, "-t", "[1,2]\n3"])
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Session is expired"));
+    }
+
+    fn write_judge_mode(path: &str, mode: &str) {
+        let code = read_file(path).replace(
+            "impl Solution {",
+            &format!("// judge={}\nimpl Solution {{", mode),
+        );
+        std::fs::write(path, code).unwrap();
+    }
+
+    #[test]
+    fn submit_problem_timeout() {
+        let env = Env::with_config(json!({
+            "judge": {"timeout": 1, "poll_interval": 50}
+        }))
+        .login();
+        let generated = pick_two_sum(&env);
+        write_judge_mode(&generated, "pending");
+
+        env.leetup()
+            .args(["submit", &generated])
+            .assert()
+            .failure()
+            .stderr(contains("Timed out after 1s waiting for judge result"));
+        assert!(env.judge.requests("/submissions/detail/1/check/").len() > 1);
+    }
+
+    #[test]
+    fn submit_problem_rate_limited() {
+        let env = Env::logged_in();
+        let generated = pick_two_sum(&env);
+        write_judge_mode(&generated, "rate_limited");
+
+        env.leetup()
+            .args(["submit", &generated])
+            .assert()
+            .success()
+            .stdout(contains("63/63"));
+        assert_eq!(2, env.judge.requests("/submissions/detail/2/check/").len());
+    }
+
+    #[test]
+    fn retry_transient_errors() {
+        let env = Env::logged_in();
+        env.set_config(json!({
+            "urls": {
+                "base": env.judge.base,
+                "problems_all": "/flaky/api/problems/all",
+                "submit": "/flaky/problems/$slug/submit/"
+            },
+            "http": {"retry_delay": 10}
+        }));
+        let generated = pick_two_sum(&env);
+        assert_eq!(2, env.judge.requests("/flaky/api/problems/all").len());
+
+        // Submissions are not idempotent, they are never retried
+        env.leetup().args(["submit", &generated]).assert().failure();
+        assert_eq!(
+            1,
+            env.judge.requests("/flaky/problems/two-sum/submit/").len()
+        );
+    }
+
+    #[test]
+    fn proxy_from_config() {
+        let env = Env::new();
+        env.set_config(json!({
+            "urls": {"base": "http://leetcode.invalid"},
+            "http": {"proxy": env.judge.base}
+        }));
+        env.leetup()
+            .args(["show", "two-sum"])
+            .assert()
+            .success()
+            .stdout(contains("Two Sum"));
+        let requests = env.judge.requests("/api/problems/all");
+        assert_eq!(
+            "http://leetcode.invalid/api/problems/all",
+            requests[0].target
+        );
+    }
+
+    #[test]
+    fn invalid_ca_certificate() {
+        let env = Env::new();
+        env.set_config(json!({"http": {"ca_certificate": env.path("missing.pem")}}));
+        env.leetup()
+            .args(["show", "two-sum"])
+            .assert()
+            .failure()
+            .stderr(contains("Unable to read CA certificate"));
+
+        std::fs::write(env.path("ca.pem"), "not a certificate").unwrap();
+        env.set_config(json!({"http": {"ca_certificate": env.path("ca.pem")}}));
+        env.leetup()
+            .args(["show", "two-sum"])
+            .assert()
+            .failure()
+            .stderr(contains("No PEM certificate found"));
+    }
+
+    fn solve_two_sum(path: &str) {
+        let code = read_file(path).replace(
+            "        \n",
+            r#"        let mut seen = std::collections::HashMap::new();
+        for (i, num) in nums.iter().enumerate() {
+            if let Some(&j) = seen.get(&(target - num)) {
+                return vec![j as i32, i as i32];
+            }
+            seen.insert(num, i);
+        }
+        println!("no solution");
+        vec![]
+"#,
+        );
+        std::fs::write(path, code).unwrap();
+    }
+
+    #[test]
+    fn run_problem_time_limit() {
+        let env = Env::with_config(json!({"run": {"timeout": 1}}));
+        let generated = pick_two_sum(&env);
+        let code = read_file(&generated).replace("        \n", "        loop {}\n");
+        std::fs::write(&generated, code).unwrap();
+
+        env.leetup()
+            .args(["run", &generated, "--offline", "-t", "[2,7,11,15]\n9"])
             .assert()
+            .success()
+            .stdout(contains("Time Limit Exceeded\nKilled after 1s"));
+    }
+
+    #[test]
+    fn pick_problem_with_tests() {
+        let env = Env::new();
+        let generated = pick_two_sum(&env);
+        let buffer = read_file(&generated);
+        assert!(
+            buffer.contains("// @leetup=inject:tests\nstruct Solution;\n// @leetup=inject:tests")
+        );
+        assert!(buffer.contains("assert_eq!(Solution::two_sum(nums, target), vec![1, 2]);"));
+
+        // Generated tests run as they are
+        solve_two_sum(&generated);
+        Command::new("rustc")
+            .args(["--edition", "2018", "--test", "-o", "two-sum", &generated])
+            .current_dir(env.work_dir.path())
+            .assert()
+            .success();
+        Command::new(env.path("two-sum"))
+            .assert()
+            .success()
+            .stdout(contains("2 passed"));
+    }
+
+    #[test]
+    fn run_problem_locally() {
+        let env = Env::new();
+        let generated = pick_two_sum(&env);
+        solve_two_sum(&generated);
+
+        // Local runs only need the problem cached by `pick`
+        let bytes = env
+            .leetup()
+            .args([
+                "run",
+                &generated,
+                "--offline",
+                "-t",
+                "[2,7,11,15]\n9\n[1,2]\n7",
+            ])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let output = strip_ansi(&bytes);
+        let lines: Vec<&str> = output.lines().map(str::trim).collect();
+        assert_eq!(
+            lines[..6],
+            ["Case 1:", "Input:", "[2,7,11,15]", "9", "", "Output: [0,1]"]
+        );
+        assert!(lines.contains(&"Case 2:"));
+        assert!(output.contains("Output: []\nStdout: \nno solution"));
+        assert!(env
+            .judge
+            .requests("/problems/two-sum/interpret_solution/")
+            .is_empty());
+    }
+
+    #[test]
+    fn pick_problem_with_helpers() {
+        let env = Env::new();
+        env.leetup()
+            .args(["pick", "-l", "rust", "226"])
+            .assert()
+            .success();
+        let generated = env.path("invert-binary-tree.rs");
+        let code = read_file(&generated);
+        assert!(code.contains("// @leetup=inject:before_code_ex\n#[derive(Debug, PartialEq, Eq)]\npub struct TreeNode"));
+        assert!(code.contains("pub fn to_tree(values: Vec<Option<i32>>)"));
+        assert!(!code.contains("pub struct ListNode"));
+        assert!(code.contains(
+            "assert_eq!(Solution::invert_tree(root), to_tree(vec![Some(2), Some(3), Some(1)]));"
+        ));
+
+        let code = code.replace(
+            "-> Option<Rc<RefCell<TreeNode>>> {\n        \n",
+            r#"-> Option<Rc<RefCell<TreeNode>>> {
+        if let Some(ref node) = root {
+            let mut node = node.borrow_mut();
+            let left = node.left.take();
+            let right = node.right.take();
+            node.left = Self::invert_tree(right);
+            node.right = Self::invert_tree(left);
+        }
+        root
+"#,
+        );
+        std::fs::write(&generated, code).unwrap();
+
+        Command::new("rustc")
+            .args(["--edition", "2018", "--test", "-o", "invert"])
+            .arg(&generated)
+            .current_dir(env.work_dir.path())
+            .assert()
+            .success();
+        Command::new(env.path("invert"))
+            .assert()
+            .success()
+            .stdout(contains("3 passed"));
+
+        let bytes = env
+            .leetup()
+            .args(["run", "invert-binary-tree.rs"])
+            .assert()
+            .success()
             .get_output()
             .stdout
             .clone();
-        let stripped_output = strip_ansi_escapes::strip(bytes);
-        let generated_path = String::from_utf8(stripped_output)
+        let output = strip_ansi(&bytes);
+        assert!(output.contains("Output: [4,7,2,9,6,3,1]"));
+        assert!(output.contains("Output: []"));
+
+        // Helpers are not submitted
+        let env = env.login();
+        env.leetup()
+            .args(["submit", "invert-binary-tree.rs"])
+            .assert()
+            .success();
+        let body = env.judge.requests("/problems/invert-binary-tree/submit/")[0].json();
+        let typed_code = body["typed_code"].as_str().unwrap();
+        assert!(typed_code.contains("pub fn invert_tree"));
+        assert!(!typed_code.contains("pub fn to_tree"));
+    }
+
+    #[test]
+    fn pick_problem_cargo_project() {
+        let env = Env::with_config(json!({
+            "pick_hook": {
+                "rust": {"working_dir": "lc", "project_layout": "cargo"}
+            }
+        }))
+        .login();
+        env.leetup()
+            .args(["pick", "-l", "rust", "1"])
+            .assert()
+            .success()
+            .stdout(contains("Created Cargo project"));
+        let manifest = read_file(env.path("lc/Cargo.toml"));
+        assert!(manifest.contains("edition = \"2021\""));
+
+        let generated = env.path("lc/tests/two-sum.rs");
+        let code = read_file(&generated);
+        assert!(code.contains("// @leetup=inject:before_code_ex\nstruct Solution;\n"));
+        assert_eq!(1, code.matches("struct Solution;").count());
+        solve_two_sum(generated.to_str().unwrap());
+
+        Command::new("cargo")
+            .args(["test", "--offline", "--test", "two-sum"])
+            .current_dir(env.path("lc"))
+            .assert()
+            .success()
+            .stdout(contains("2 passed"));
+
+        // The problem is still found from the `@leetup=info` header
+        env.leetup()
+            .args(["submit", "lc/tests/two-sum.rs"])
+            .assert()
+            .success();
+        let body = env.judge.requests("/problems/two-sum/submit/")[0].json();
+        assert!(!body["typed_code"]
+            .as_str()
             .unwrap()
-            .replace("Generated: ", "");
-        let result = generated_path.trim_end();
-        let mut generated_file = File::open(result).unwrap();
-        let mut buffer = String::new();
-        generated_file.read_to_string(&mut buffer).unwrap();
-        assert!(buffer.contains("// @leetup=custom\n// @leetup=info id=1 lang=rust slug=two-sum"));
-        assert!(buffer.contains("// @leetup=code\n"));
+            .contains("struct Solution;"));
+
+        // Picking another problem reuses the project
+        env.leetup()
+            .args(["pick", "-l", "rust", "226"])
+            .assert()
+            .success()
+            .stdout(contains("Created Cargo project").not());
+        let generated = env.path("lc/tests/invert-binary-tree.rs");
+        let code = read_file(&generated).replace("        \n", "        root\n");
+        std::fs::write(&generated, code).unwrap();
+
+        // New problems are picked up by cargo without editing the manifest
+        Command::new("cargo")
+            .args(["test", "--offline", "--no-run"])
+            .current_dir(env.path("lc"))
+            .assert()
+            .success()
+            .stderr(contains("tests/two-sum.rs").and(contains("tests/invert-binary-tree.rs")));
     }
 }
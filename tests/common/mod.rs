@@ -0,0 +1,472 @@
+//! In-process mock of the Online Judge, serving canned responses so the CLI
+//! can be tested end-to-end without network.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use assert_cmd::Command;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Request target as sent, an absolute URL when sent through a proxy.
+    pub target: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or_default()
+    }
+}
+
+/// Mock judge listening on a random local port.
+pub struct MockJudge {
+    pub base: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockJudge {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+
+        thread::spawn(move || {
+            // Judge results are pending on the first check
+            let mut checks: HashMap<String, usize> = HashMap::new();
+            for stream in listener.incoming().flatten() {
+                if let Some(request) = read_request(&stream) {
+                    let (status, body) = route(&request, &mut checks);
+                    recorded.lock().unwrap().push(request);
+                    write_response(stream, status, body);
+                }
+            }
+        });
+
+        MockJudge { base, requests }
+    }
+
+    /// Number of requests received so far.
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Requests received so far matching `path`.
+    pub fn requests(&self, path: &str) -> Vec<Request> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.path == path)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Isolated leetup home and working directory talking to a `MockJudge`.
+pub struct Env {
+    pub judge: MockJudge,
+    pub home: TempDir,
+    pub work_dir: TempDir,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        Env::with_config(json!({}))
+    }
+
+    /// Environment with `config` merged into the default config.json.
+    pub fn with_config(config: Value) -> Self {
+        let env = Env {
+            judge: MockJudge::start(),
+            home: tempfile::tempdir().unwrap(),
+            work_dir: tempfile::tempdir().unwrap(),
+        };
+        env.set_config(config);
+        env
+    }
+
+    /// Replace config.json with `config` merged into the default config.
+    pub fn set_config(&self, config: Value) {
+        let mut defaults = json!({
+            "lang": "rust",
+            "urls": {
+                "base": self.judge.base,
+            }
+        });
+        if let (Some(defaults), Value::Object(config)) = (defaults.as_object_mut(), config) {
+            defaults.extend(config);
+        }
+        let config = defaults;
+        std::fs::write(self.home.path().join("config.json"), config.to_string()).unwrap();
+    }
+
+    /// Logged in environment.
+    pub fn logged_in() -> Self {
+        Env::new().login()
+    }
+
+    pub fn login(self) -> Self {
+        self.login_with("session-id")
+    }
+
+    /// Login using the given `LEETCODE_SESSION` cookie.
+    pub fn login_with(self, session: &str) -> Self {
+        self.leetup()
+            .args(["user", "-c"])
+            .write_stdin(format!("csrf-token\n{}\n", session))
+            .assert()
+            .success();
+        self
+    }
+
+    pub fn leetup(&self) -> Command {
+        let mut cmd = Command::cargo_bin("leetup").unwrap();
+        cmd.env("LEETUP_HOME", self.home.path())
+            .current_dir(self.work_dir.path());
+        cmd
+    }
+
+    pub fn path(&self, filename: &str) -> std::path::PathBuf {
+        self.work_dir.path().join(filename)
+    }
+}
+
+pub fn strip_ansi(bytes: &[u8]) -> String {
+    String::from_utf8(strip_ansi_escapes::strip(bytes)).unwrap()
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> String {
+    std::fs::read_to_string(path).unwrap()
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_owned();
+    let target = parts.next()?.to_owned();
+    // Proxied requests target an absolute URL
+    let path = match target.strip_prefix("http://") {
+        Some(url) => url.find('/').map_or("/", |i| &url[i..]),
+        None => &target,
+    };
+    let path = path.split('?').next()?.to_owned();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+        }
+    }
+
+    let len = headers
+        .get("content-length")
+        .and_then(|len| len.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        target,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_response(mut stream: TcpStream, status: u16, body: Value) {
+    let body = body.to_string();
+    // Rate limited clients are asked to retry right away
+    let retry_after = if status == 429 {
+        "Retry-After: 0\r\n"
+    } else {
+        ""
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        if status == 200 { "OK" } else { "Error" },
+        body.len(),
+        retry_after,
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn route(request: &Request, checks: &mut HashMap<String, usize>) -> (u16, Value) {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let expired = request
+        .headers
+        .get("cookie")
+        .is_some_and(|cookie| cookie.contains("LEETCODE_SESSION=expired"));
+    match (request.method.as_str(), segments.as_slice()) {
+        // Expired sessions are signed out on graphql and rejected elsewhere
+        _ if expired && segments != ["graphql"] => (403, json!({"detail": "Forbidden"})),
+        // The first request of each path under `/flaky` fails, then it is served as usual
+        (_, ["flaky", rest @ ..]) => {
+            let attempts = checks.entry(request.path.clone()).or_default();
+            *attempts += 1;
+            if *attempts == 1 {
+                return (503, json!({"detail": "Service Unavailable"}));
+            }
+            let request = Request {
+                path: format!("/{}", rest.join("/")),
+                ..request.clone()
+            };
+            route(&request, checks)
+        }
+        ("GET", ["api", "problems", "all"]) => (200, problems_all()),
+        ("POST", ["graphql"]) => graphql(request),
+        ("POST", ["problems", _, "interpret_solution"]) => (
+            200,
+            json!({"interpret_id": "runcode_1694281112.034828_DfKA6OnxO1", "test_case": ""}),
+        ),
+        ("POST", ["problems", _, "submit"]) => {
+            // Solutions can ask for a misbehaving judge through their code
+            let typed_code = request.json()["typed_code"]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned();
+            let submission_id = if typed_code.contains("judge=pending") {
+                1
+            } else if typed_code.contains("judge=rate_limited") {
+                2
+            } else {
+                1044907055
+            };
+            (200, json!({ "submission_id": submission_id }))
+        }
+        ("GET", ["submissions", "detail", id, "check"]) => {
+            let count = checks.entry(id.to_string()).or_default();
+            *count += 1;
+            if *id == "1" {
+                (200, json!({"state": "PENDING"}))
+            } else if *id == "2" && *count == 1 {
+                (429, json!({"error": "Too many requests"}))
+            } else if *count == 1 {
+                (200, json!({"state": "PENDING"}))
+            } else if *count == 2 && *id != "2" {
+                (
+                    200,
+                    json!({"state": "STARTED", "total_correct": 57, "total_testcases": 120}),
+                )
+            } else if id.starts_with("runcode") {
+                (200, run_result())
+            } else {
+                (200, submit_result())
+            }
+        }
+        _ => (404, json!({"error": "Not found"})),
+    }
+}
+
+fn graphql(request: &Request) -> (u16, Value) {
+    let body = request.json();
+    match body["operationName"].as_str() {
+        Some("getQuestionDetail") => match body["variables"]["titleSlug"].as_str() {
+            Some("two-sum") => (200, two_sum_detail()),
+            Some("invert-binary-tree") => (200, invert_tree_detail()),
+            _ => (200, json!({"data": {"question": null}})),
+        },
+        Some("globalData") => {
+            let signed_in = request
+                .headers
+                .get("cookie")
+                .is_some_and(|cookie| cookie.contains("LEETCODE_SESSION=session-id"));
+            (
+                200,
+                json!({"data": {"userStatus": {
+                    "isSignedIn": signed_in,
+                    "username": if signed_in { "leetup" } else { "" },
+                    "isPremium": false
+                }}}),
+            )
+        }
+        Some("questionOfToday") => (
+            200,
+            json!({"data": {"activeDailyCodingChallengeQuestion": {
+                "date": "2023-09-09",
+                "question": {"titleSlug": "two-sum"}
+            }}}),
+        ),
+        _ => (400, json!({"errors": [{"message": "Unknown operation"}]})),
+    }
+}
+
+fn stat_status_pair(id: usize, title: &str, slug: &str, level: u8, paid_only: bool) -> Value {
+    json!({
+        "stat": {
+            "question_id": id,
+            "question__title": title,
+            "question__title_slug": slug,
+            "question__hide": false,
+            "total_acs": 100,
+            "total_submitted": 200,
+            "frontend_question_id": id,
+            "is_new_question": false
+        },
+        "status": match id {
+            1 => json!("ac"),
+            226 => json!("notac"),
+            _ => Value::Null,
+        },
+        "difficulty": {"level": level},
+        "paid_only": paid_only,
+        "is_favor": false,
+        "frequency": 0,
+        "progress": 0
+    })
+}
+
+fn problems_all() -> Value {
+    json!({
+        "user_name": "leetup",
+        "num_solved": 1,
+        "num_total": 4,
+        "ac_easy": 1,
+        "ac_medium": 0,
+        "ac_hard": 0,
+        "stat_status_pairs": [
+            stat_status_pair(4, "Median of Two Sorted Arrays", "median-of-two-sorted-arrays", 3, false),
+            stat_status_pair(1, "Two Sum", "two-sum", 1, false),
+            stat_status_pair(2, "Add Two Numbers", "add-two-numbers", 2, true),
+            stat_status_pair(226, "Invert Binary Tree", "invert-binary-tree", 1, false),
+        ],
+        "frequency_high": 0,
+        "frequency_mid": 0,
+        "category_slug": "all"
+    })
+}
+
+fn two_sum_detail() -> Value {
+    let code_definition = json!([
+        {"value": "rust", "text": "Rust", "defaultCode": "impl Solution {\n    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {\n        \n    }\n}"},
+        {"value": "python3", "text": "Python3", "defaultCode": "class Solution:\n    def twoSum(self, nums: List[int], target: int) -> List[int]:\n        "}
+    ]);
+    let meta_data = json!({
+        "name": "twoSum",
+        "params": [{"name": "nums", "type": "integer[]"}, {"name": "target", "type": "integer"}],
+        "return": {"type": "integer[]", "size": 2}
+    });
+
+    json!({"data": {"question": {
+        "questionFrontendId": "1",
+        "title": "Two Sum",
+        "titleSlug": "two-sum",
+        "difficulty": "Easy",
+        "content": "<p>Given an array of integers <code>nums</code>&nbsp;and an integer <code>target</code>, return <em>indices of the two numbers such that they add up to <code>target</code></em>.</p>\n<p><strong>Example 1:</strong></p>\n<pre>\n<strong>Input:</strong> nums = [2,7,11,15], target = 9\n<strong>Output:</strong> [0,1]\n</pre>\n<p><strong>Example 2:</strong></p>\n<pre>\n<strong>Input:</strong> nums = [3,2,4], target = 6\n<strong>Output:</strong> [1,2]\n</pre>\n<p><strong>Constraints:</strong></p>\n<ul><li><code>2 &lt;= nums.length &lt;= 10<sup>4</sup></code></li></ul>",
+        "stats": "{\"totalAccepted\": \"12.6M\", \"totalSubmission\": \"24.9M\", \"acRate\": \"50.6%\"}",
+        "likes": 52000,
+        "dislikes": 1700,
+        "codeDefinition": code_definition.to_string(),
+        "sampleTestCase": "[2,7,11,15]\n9",
+        "exampleTestcases": "[2,7,11,15]\n9\n[3,2,4]\n6",
+        "enableRunCode": true,
+        "metaData": meta_data.to_string(),
+        "translatedTitle": null,
+        "translatedContent": null,
+        "hints": ["A really brute force way would be to search for all possible pairs of numbers."],
+        "topicTags": [{"name": "Array", "slug": "array"}, {"name": "Hash Table", "slug": "hash-table"}]
+    }}})
+}
+
+fn invert_tree_detail() -> Value {
+    let code_definition = json!([
+        {"value": "rust", "text": "Rust", "defaultCode": "// Definition for a binary tree node.\n// #[derive(Debug, PartialEq, Eq)]\n// pub struct TreeNode {\n//   pub val: i32,\n//   pub left: Option<Rc<RefCell<TreeNode>>>,\n//   pub right: Option<Rc<RefCell<TreeNode>>>,\n// }\nuse std::rc::Rc;\nuse std::cell::RefCell;\nimpl Solution {\n    pub fn invert_tree(root: Option<Rc<RefCell<TreeNode>>>) -> Option<Rc<RefCell<TreeNode>>> {\n        \n    }\n}"},
+        {"value": "python3", "text": "Python3", "defaultCode": "# Definition for a binary tree node.\n# class TreeNode:\n#     def __init__(self, val=0, left=None, right=None):\n#         self.val = val\nclass Solution:\n    def invertTree(self, root: Optional[TreeNode]) -> Optional[TreeNode]:\n        "},
+        {"value": "cpp", "text": "C++", "defaultCode": "/**\n * Definition for a binary tree node.\n * struct TreeNode {\n *     int val;\n * };\n */\nclass Solution {\npublic:\n    TreeNode* invertTree(TreeNode* root) {\n        \n    }\n};"}
+    ]);
+    let meta_data = json!({
+        "name": "invertTree",
+        "params": [{"name": "root", "type": "TreeNode"}],
+        "return": {"type": "TreeNode"}
+    });
+
+    json!({"data": {"question": {
+        "questionFrontendId": "226",
+        "title": "Invert Binary Tree",
+        "titleSlug": "invert-binary-tree",
+        "difficulty": "Easy",
+        "content": "<p>Given the <code>root</code> of a binary tree, invert the tree, and return <em>its root</em>.</p>\n<pre>\n<strong>Input:</strong> root = [4,2,7,1,3,6,9]\n<strong>Output:</strong> [4,7,2,9,6,3,1]\n</pre>\n<pre>\n<strong>Input:</strong> root = [2,1,3]\n<strong>Output:</strong> [2,3,1]\n</pre>\n<pre>\n<strong>Input:</strong> root = []\n<strong>Output:</strong> []\n</pre>",
+        "stats": "{\"totalAccepted\": \"2.1M\", \"totalSubmission\": \"2.7M\", \"acRate\": \"77.1%\"}",
+        "likes": 13000,
+        "dislikes": 180,
+        "codeDefinition": code_definition.to_string(),
+        "sampleTestCase": "[4,2,7,1,3,6,9]",
+        "exampleTestcases": "[4,2,7,1,3,6,9]\n[2,1,3]\n[]",
+        "enableRunCode": true,
+        "metaData": meta_data.to_string(),
+        "translatedTitle": null,
+        "translatedContent": null,
+        "topicTags": [{"name": "Tree", "slug": "tree"}]
+    }}})
+}
+
+fn run_result() -> Value {
+    json!({
+        "status_code": 10,
+        "lang": "rust",
+        "run_success": true,
+        "status_runtime": "0 ms",
+        "memory": 2096000,
+        "code_answer": ["[0,1]"],
+        "expected_code_answer": ["[0,1]"],
+        "correct_answer": true,
+        "compare_result": "1",
+        "total_correct": 1,
+        "total_testcases": 1,
+        "runtime_percentile": null,
+        "status_memory": "2.1 MB",
+        "memory_percentile": null,
+        "pretty_lang": "Rust",
+        "submission_id": "runcode_1694281112.034828_DfKA6OnxO1",
+        "status_msg": "Accepted",
+        "state": "SUCCESS"
+    })
+}
+
+fn submit_result() -> Value {
+    json!({
+        "status_code": 10,
+        "lang": "rust",
+        "run_success": true,
+        "status_runtime": "0 ms",
+        "memory": 1928000,
+        "question_id": "1",
+        "compare_result": "111",
+        "code_output": "",
+        "std_output": "",
+        "last_testcase": "",
+        "expected_output": "",
+        "finished": true,
+        "total_correct": 63,
+        "total_testcases": 63,
+        "runtime_percentile": 100,
+        "status_memory": "1.9 MB",
+        "memory_percentile": 91.8367,
+        "pretty_lang": "Rust",
+        "submission_id": "1044907055",
+        "status_msg": "Accepted",
+        "state": "SUCCESS"
+    })
+}